@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+use crate::item_view::ItemView;
+use crate::relic::RelicInventory;
+use crate::structures::Data;
+use crate::Tracked;
+
+/// How a prime part can currently be obtained, from easiest to hardest
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Availability
+{
+	/// Relics drop from missions
+	Available,
+	/// Relics can be bought from Varzia
+	Resurgence,
+	/// Vaulted, but some of the relics are owned
+	OwnedRelics,
+	Vaulted
+}
+
+impl Availability
+{
+	pub const ALL: [Self; 4] = [Self::Available, Self::Resurgence, Self::OwnedRelics, Self::Vaulted];
+}
+
+impl Display for Availability
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			Self::Available=>"Available".fmt(f),
+			Self::Resurgence=>"Resurgence".fmt(f),
+			Self::OwnedRelics=>"Owned Relics".fmt(f),
+			Self::Vaulted=>"Vaulted".fmt(f)
+		}
+	}
+}
+
+/// Availability of a single part, or None if it doesn't come from relics
+pub fn of_item(db: &Data, relics: &RelicInventory, item: &impl ItemView) -> Option<Availability>
+{
+	if !item.active_relics().is_empty()
+	{
+		return Some(Availability::Available)
+	}
+	if !item.resurgence_relics().is_empty()
+	{
+		return Some(Availability::Resurgence)
+	}
+
+	let mut containing = db.relics_containing(item.unique_name());
+	if let Some(recipe) = item.recipe_unique_name()
+	{
+		containing.extend(db.relics_containing(recipe));
+	}
+	if containing.is_empty()
+	{
+		return None
+	}
+	let owned = containing.iter()
+		.filter_map(|r|relics.get(r))
+		.any(|counts|counts.values().any(|&c|c > 0));
+	Some(if owned {Availability::OwnedRelics} else {Availability::Vaulted})
+}
+
+/// Availability of a tracked item, which is only as available as its
+/// hardest to get part
+pub fn of_tracked(db: &Data, relics: &RelicInventory, tracked: &Tracked) -> Option<Availability>
+{
+	tracked.recipes
+		.iter()
+		.flat_map(|(recipe, components)|
+		{
+			let components = components.iter()
+				.flat_map(|(c, _)|of_item(db, relics, c));
+			of_item(db, relics, recipe).into_iter().chain(components)
+		})
+		.max()
+}
@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Result, Context};
+
+use crate::structures::{BuildInfo, Count, Data, UniqueName};
+
+/// A fully expanded crafting tree, down to the raw resources
+#[derive(Debug)]
+pub struct BuildTree
+{
+	pub unique_name: UniqueName,
+	/// Total amount needed, multiplied along the path from the root
+	pub count: Count,
+	/// Amount used by a single craft of the parent
+	pub per_craft: Count,
+	/// Recipe used to craft this node. Leaf resources have none
+	pub recipe: Option<UniqueName>,
+	pub build_info: BuildInfo,
+	pub children: Vec<BuildTree>
+}
+
+impl BuildTree
+{
+	/// Expand a recipe using the recipe's unique name
+	pub fn new(db: &Data, recipe_unique_name: UniqueName) -> Result<Self>
+	{
+		let result_unique_name = db.recipe_result(recipe_unique_name.clone())
+			.context("Looking for recipe result")?;
+		let mut path = vec![result_unique_name.clone()];
+		let tree = Self::craft(
+			db,
+			result_unique_name,
+			recipe_unique_name,
+			1.into(),
+			1.into(),
+			&mut path);
+		Ok(tree)
+	}
+
+	/// A single item that isn't crafted
+	pub fn leaf(unique_name: UniqueName, per_craft: Count, count: Count) -> Self
+	{
+		Self
+		{
+			unique_name,
+			count,
+			per_craft,
+			recipe: None,
+			build_info: BuildInfo::default(),
+			children: vec![]
+		}
+	}
+
+	fn craft(
+		db: &Data,
+		unique_name: UniqueName,
+		recipe_unique_name: UniqueName,
+		per_craft: Count,
+		count: Count,
+		path: &mut Vec<UniqueName>) -> Self
+	{
+		let build_info = db.recipe_build_info(recipe_unique_name.clone())
+			.unwrap_or_default();
+		let crafts = crafts_for(&build_info, count.to_u32());
+		let mut children = vec![];
+		for (ingredient, ingredient_count) in db.requirements(recipe_unique_name.clone())
+		{
+			let count = Count(crafts) * ingredient_count;
+			let child = Self::expand(db, ingredient, ingredient_count, count, path);
+			children.push(child);
+		}
+		Self
+		{
+			unique_name,
+			count,
+			per_craft,
+			recipe: Some(recipe_unique_name),
+			build_info,
+			children
+		}
+	}
+
+	fn expand(
+		db: &Data,
+		unique_name: UniqueName,
+		per_craft: Count,
+		count: Count,
+		path: &mut Vec<UniqueName>) -> Self
+	{
+		match db.recipe(unique_name.clone())
+		{
+			// Anything already being crafted further up this branch is
+			// treated as a leaf to avoid recursing forever
+			Some(recipe_unique_name) if !path.contains(&unique_name) =>
+			{
+				path.push(unique_name.clone());
+				let tree = Self::craft(db, unique_name, recipe_unique_name, per_craft, count, path);
+				path.pop();
+				tree
+			},
+			_ => Self::leaf(unique_name, per_craft, count)
+		}
+	}
+
+	/// Number of foundry crafts needed to build this node
+	pub fn crafts(&self) -> u32
+	{
+		crafts_for(&self.build_info, self.count.to_u32())
+	}
+
+	/// Total count of every leaf resource in the tree, blueprints included
+	pub fn leaves(&self) -> HashMap<UniqueName, Count>
+	{
+		let mut leaves = HashMap::new();
+		self.collect_leaves(&mut leaves);
+		leaves
+	}
+
+	fn collect_leaves(&self, leaves: &mut HashMap<UniqueName, Count>)
+	{
+		let Some(recipe_unique_name) = &self.recipe
+		else
+		{
+			*leaves.entry(self.unique_name.clone()).or_default() += self.count;
+			return
+		};
+
+		let blueprints = blueprints_for(&self.build_info, self.crafts());
+		*leaves.entry(recipe_unique_name.clone()).or_default() += blueprints.into();
+		for child in &self.children
+		{
+			child.collect_leaves(leaves);
+		}
+	}
+
+	/// Whether the item or its blueprint is used anywhere in the tree
+	pub fn contains(&self, unique_name: &UniqueName) -> bool
+	{
+		self.unique_name == *unique_name
+			|| self.recipe.as_ref() == Some(unique_name)
+			|| self.children.iter().any(|c|c.contains(unique_name))
+	}
+
+	/// Total credits charged by the foundry across the whole tree
+	pub fn credits(&self) -> u64
+	{
+		let own = u64::from(self.crafts()) * u64::from(self.build_info.credits);
+		own + self.children.iter().map(|c|c.credits()).sum::<u64>()
+	}
+
+	/// Foundry time along the longest chain of crafts. Independent
+	/// components are assumed to be built in parallel
+	pub fn build_time(&self) -> Duration
+	{
+		let children = self.children.iter()
+			.map(|c|c.build_time())
+			.max()
+			.unwrap_or_default();
+		children + self.build_info.build_time
+	}
+
+	/// Raw resources still missing once owned items are used up. Anything
+	/// used is taken out of `owned` so it can be shared across trees
+	pub fn missing(
+		&self,
+		owned: &mut HashMap<UniqueName, u32>,
+		missing: &mut HashMap<UniqueName, Count>)
+	{
+		self.collect_missing(self.count.to_u32(), owned, missing);
+	}
+
+	fn collect_missing(
+		&self,
+		needed: u32,
+		owned: &mut HashMap<UniqueName, u32>,
+		missing: &mut HashMap<UniqueName, Count>)
+	{
+		let needed = take_owned(&self.unique_name, needed, owned);
+		if needed == 0
+		{
+			return
+		}
+
+		let Some(recipe_unique_name) = &self.recipe
+		else
+		{
+			*missing.entry(self.unique_name.clone()).or_default() += needed.into();
+			return
+		};
+
+		let crafts = crafts_for(&self.build_info, needed);
+		let blueprints = blueprints_for(&self.build_info, crafts);
+		let blueprints = take_owned(recipe_unique_name, blueprints, owned);
+		if blueprints != 0
+		{
+			*missing.entry(recipe_unique_name.clone()).or_default() += blueprints.into();
+		}
+		for child in &self.children
+		{
+			child.collect_missing(child.per_craft.to_u32() * crafts, owned, missing);
+		}
+	}
+}
+
+fn crafts_for(build_info: &BuildInfo, count: u32) -> u32
+{
+	count.div_ceil(build_info.quantity.max(1))
+}
+
+fn blueprints_for(build_info: &BuildInfo, crafts: u32) -> u32
+{
+	if build_info.consume_on_build
+	{
+		crafts
+	}
+	else
+	{
+		crafts.min(1)
+	}
+}
+
+/// Use up as much of `needed` as possible from `owned`, returning what is left
+fn take_owned(unique_name: &UniqueName, needed: u32, owned: &mut HashMap<UniqueName, u32>) -> u32
+{
+	let available = owned.entry(unique_name.clone()).or_default();
+	let used = needed.min(*available);
+	*available -= used;
+	needed - used
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	/// An item built from parts, where the parts are built from an alloy
+	/// that itself needs a part
+	fn data() -> Data
+	{
+		let mut data = Data::default();
+		data.add_recipe(
+			"/ItemBlueprint",
+			"/Item",
+			BuildInfo{credits: 25_000, ..BuildInfo::default()},
+			&[("/Part", 3), ("/Ferrite", 100)]);
+		data.add_recipe(
+			"/PartBlueprint",
+			"/Part",
+			BuildInfo{credits: 1_000, quantity: 2, ..BuildInfo::default()},
+			&[("/Alloy", 5)]);
+		data.add_recipe("/AlloyBlueprint", "/Alloy", BuildInfo::default(), &[("/Part", 1)]);
+		data
+	}
+
+	fn counts(counts: &[(&str, u32)]) -> HashMap<UniqueName, Count>
+	{
+		counts.iter()
+			.map(|&(unique_name, count)|(unique_name.into(), count.into()))
+			.collect()
+	}
+
+	#[test]
+	fn cycles_stop_at_an_item_already_being_crafted()
+	{
+		let tree = BuildTree::new(&data(), "/ItemBlueprint".into()).unwrap();
+		let part = &tree.children[0];
+		let alloy = &part.children[0];
+		let inner_part = &alloy.children[0];
+		assert_eq!(part.recipe, Some("/PartBlueprint".into()));
+		assert_eq!(alloy.recipe, Some("/AlloyBlueprint".into()));
+		assert_eq!(inner_part.unique_name, UniqueName::from("/Part"));
+		assert_eq!(inner_part.recipe, None);
+		assert!(inner_part.children.is_empty());
+	}
+
+	#[test]
+	fn multi_quantity_recipes_round_crafts_up()
+	{
+		let tree = BuildTree::new(&data(), "/ItemBlueprint".into()).unwrap();
+		let part = &tree.children[0];
+		// Three parts at two per craft
+		assert_eq!(part.count, 3.into());
+		assert_eq!(part.crafts(), 2);
+		assert_eq!(part.children[0].count, 10.into());
+		assert_eq!(tree.credits(), 25_000 + 2 * 1_000);
+		assert_eq!(tree.leaves(), counts(&[
+			("/ItemBlueprint", 1),
+			("/PartBlueprint", 2),
+			("/AlloyBlueprint", 10),
+			("/Part", 10),
+			("/Ferrite", 100)]));
+	}
+
+	#[test]
+	fn missing_shares_owned_items_across_trees()
+	{
+		let db = data();
+		let trees = [
+			BuildTree::new(&db, "/ItemBlueprint".into()).unwrap(),
+			BuildTree::new(&db, "/ItemBlueprint".into()).unwrap()];
+		let mut owned = HashMap::from([("/Part".into(), 4), ("/Ferrite".into(), 150)]);
+		let mut missing = HashMap::new();
+		for tree in &trees
+		{
+			tree.missing(&mut owned, &mut missing);
+		}
+
+		// The first tree takes three parts, leaving one for the second, which
+		// needs a craft of two more
+		assert_eq!(missing, counts(&[
+			("/ItemBlueprint", 2),
+			("/PartBlueprint", 1),
+			("/AlloyBlueprint", 5),
+			("/Part", 5),
+			("/Ferrite", 50)]));
+		assert_eq!(owned[&UniqueName::from("/Part")], 0);
+		assert_eq!(owned[&UniqueName::from("/Ferrite")], 0);
+	}
+}
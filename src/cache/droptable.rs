@@ -1,36 +1,479 @@
 use std::path::Path;
 
 use anyhow::{Context, Result};
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
 
-fn is_relic(item: &str) -> bool
+use crate::drop::{DropRarity, EnemyDropKind, RewardSource, Rotation};
+use crate::relic::Refinement;
+
+const REWARD_SOURCES: [(&str, RewardSource); 8] = [
+	("keyRewards", RewardSource::Key),
+	("transientRewards", RewardSource::Transient),
+	("sortieRewards", RewardSource::Sortie),
+	("cetusRewards", RewardSource::Cetus),
+	("solarisRewards", RewardSource::OrbVallis),
+	("deimosRewards", RewardSource::CambionDrift),
+	("zarimanRewards", RewardSource::Zariman),
+	("entratiLabRewards", RewardSource::EntratiLabs)];
+
+const ENEMY_DROPS: [(&str, EnemyDropKind); 3] = [
+	("modByAvatar", EnemyDropKind::Mod),
+	("blueprintByAvatar", EnemyDropKind::Blueprint),
+	("resourceByAvatar", EnemyDropKind::Resource)];
+
+#[derive(Clone, Debug)]
+pub struct Reward
+{
+	pub item: String,
+	pub rarity: DropRarity,
+	/// Percent
+	pub chance: f32
+}
+
+#[derive(Clone, Debug)]
+pub struct MissionReward
+{
+	pub planet: String,
+	pub node: String,
+	pub mission_type: String,
+	pub rotation: Option<Rotation>,
+	pub reward: Reward
+}
+
+#[derive(Clone, Debug)]
+pub struct RelicReward
+{
+	pub relic: String,
+	pub refinement: Refinement,
+	pub reward: Reward
+}
+
+#[derive(Clone, Debug)]
+pub struct LocationReward
+{
+	pub source: RewardSource,
+	pub location: String,
+	pub rotation: Option<Rotation>,
+	pub stage: Option<String>,
+	pub reward: Reward
+}
+
+#[derive(Clone, Debug)]
+pub struct EnemyDrop
+{
+	pub kind: EnemyDropKind,
+	pub enemy: String,
+	/// Percent chance of the enemy dropping anything from its table
+	pub enemy_chance: f32,
+	pub reward: Reward
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Droptable
+{
+	pub mission_rewards: Vec<MissionReward>,
+	pub relic_rewards: Vec<RelicReward>,
+	pub location_rewards: Vec<LocationReward>,
+	pub enemy_drops: Vec<EnemyDrop>
+}
+
+enum Row
+{
+	/// Text of every th cell
+	Header(Vec<String>),
+	/// Text of every td cell
+	Item(Vec<String>)
+}
+
+impl Row
+{
+	fn new(tr: ElementRef) -> Option<Self>
+	{
+		let cell_selector = Selector::parse("th, td").unwrap();
+		let mut headers = vec![];
+		let mut items = vec![];
+		for cell in tr.select(&cell_selector)
+		{
+			let text = cell.text().collect::<String>().trim().to_owned();
+			match cell.value().name()
+			{
+				"th" if !text.is_empty() => headers.push(text),
+				"td" => items.push(text),
+				_ => {}
+			}
+		}
+
+		if !headers.is_empty()
+		{
+			Some(Self::Header(headers))
+		}
+		else if items.iter().any(|i|!i.is_empty())
+		{
+			Some(Self::Item(items))
+		}
+		else
+		{
+			None
+		}
+	}
+}
+
+impl TryFrom<&[String]> for Reward
+{
+	type Error = anyhow::Error;
+
+	/// The last two cells of an item row, e.g. `Lith C7 Relic | Uncommon (25.00%)`
+	fn try_from(cells: &[String]) -> Result<Self, Self::Error>
+	{
+		let [.., item, chance] = cells
+		else
+		{
+			anyhow::bail!("Reward row is too short: {cells:?}");
+		};
+		let (rarity, chance) = chance.rsplit_once(" (")
+			.with_context(||format!("Unexpected drop chance: {chance}"))?;
+		let rarity = rarity.try_into()?;
+		let chance = parse_percent(chance.trim_end_matches(')'))?;
+		Ok(Self{item: item.to_owned(), rarity, chance})
+	}
+}
+
+fn parse_percent(text: &str) -> Result<f32>
+{
+	text.trim()
+		.trim_end_matches('%')
+		.parse()
+		.with_context(||format!("Unexpected percentage: {text}"))
+}
+
+/// (Parsed rows, Number of item rows that could not be parsed)
+type Section<T> = (Vec<T>, usize);
+
+/// Rows of the table directly following the header with the given id
+fn rows(document: &Html, section: &str) -> Option<Vec<Row>>
+{
+	let table_selector = Selector::parse(&format!("#{section}~table"))
+		.unwrap();
+	let row_selector = Selector::parse("tr").unwrap();
+	let table = document.select(&table_selector).next()?;
+	let rows = table.select(&row_selector)
+		.flat_map(Row::new)
+		.collect();
+	Some(rows)
+}
+
+/// Split `Mercury/Apollodorus (Survival)` into planet, node and mission type
+fn parse_location(text: &str) -> (String, String, String)
+{
+	let (location, mission_type) = text.rsplit_once(" (")
+		.map(|(l, m)|(l, m.trim_end_matches(')')))
+		.unwrap_or((text, ""));
+	let (planet, node) = location.split_once('/')
+		.unwrap_or(("", location));
+	(planet.to_owned(), node.to_owned(), mission_type.to_owned())
+}
+
+fn mission_rewards(rows: Vec<Row>) -> Section<MissionReward>
+{
+	let mut location = Default::default();
+	let mut rotation = None;
+	let mut rewards = vec![];
+	let mut skipped = 0;
+	for row in rows
+	{
+		match row
+		{
+			Row::Header(h) => match Rotation::try_from(h[0].as_str())
+			{
+				Ok(r) => rotation = Some(r),
+				Err(_) =>
+				{
+					location = parse_location(&h[0]);
+					rotation = None;
+				}
+			},
+			Row::Item(cells) =>
+			{
+				let Ok(reward) = Reward::try_from(cells.as_slice()) else {skipped += 1; continue};
+				let (planet, node, mission_type) = location.clone();
+				rewards.push(MissionReward{planet, node, mission_type, rotation, reward});
+			}
+		}
+	}
+	(rewards, skipped)
+}
+
+fn relic_rewards(rows: Vec<Row>) -> Section<RelicReward>
 {
-	item.starts_with("Lith")
-	||item.starts_with("Meso")
-	||item.starts_with("Neo")
-	||item.starts_with("Axi")
+	let mut relic = None;
+	let mut rewards = vec![];
+	let mut skipped = 0;
+	for row in rows
+	{
+		match row
+		{
+			Row::Header(h) =>
+			{
+				relic = h[0].rsplit_once(" (")
+					.and_then(|(name, refinement)|
+					{
+						let refinement = refinement.trim_end_matches(')')
+							.try_into()
+							.ok()?;
+						Some((name.to_owned(), refinement))
+					});
+			},
+			Row::Item(cells) =>
+			{
+				let Some((relic, refinement)) = &relic else {skipped += 1; continue};
+				let Ok(reward) = Reward::try_from(cells.as_slice()) else {skipped += 1; continue};
+				rewards.push(RelicReward{relic: relic.clone(), refinement: *refinement, reward});
+			}
+		}
+	}
+	(rewards, skipped)
 }
 
-pub fn active_relics(file_path: &Path) -> Result<Vec<String>>
+fn location_rewards(source: RewardSource, rows: Vec<Row>) -> Section<LocationReward>
+{
+	let mut location = String::new();
+	let mut rotation = None;
+	let mut stage = None;
+	let mut rewards = vec![];
+	let mut skipped = 0;
+	for row in rows
+	{
+		match row
+		{
+			Row::Header(h) if h[0].contains("Stage") => stage = Some(h[0].clone()),
+			Row::Header(h) => match Rotation::try_from(h[0].as_str())
+			{
+				Ok(r) =>
+				{
+					rotation = Some(r);
+					stage = None;
+				},
+				Err(_) =>
+				{
+					location = h[0].clone();
+					rotation = None;
+					stage = None;
+				}
+			},
+			Row::Item(cells) =>
+			{
+				let Ok(reward) = Reward::try_from(cells.as_slice()) else {skipped += 1; continue};
+				rewards.push(LocationReward
+				{
+					source,
+					location: location.clone(),
+					rotation,
+					stage: stage.clone(),
+					reward
+				});
+			}
+		}
+	}
+	(rewards, skipped)
+}
+
+fn enemy_drops(kind: EnemyDropKind, rows: Vec<Row>) -> Section<EnemyDrop>
+{
+	let mut enemy = None;
+	let mut drops = vec![];
+	let mut skipped = 0;
+	for row in rows
+	{
+		match row
+		{
+			// `Arid Eviscerator | Mod Drop Chance: 3.00%`
+			Row::Header(h) =>
+			{
+				enemy = h.get(1)
+					.and_then(|c|c.rsplit_once(": "))
+					.and_then(|(_, c)|parse_percent(c).ok())
+					.map(|chance|(h[0].clone(), chance));
+			},
+			Row::Item(cells) =>
+			{
+				let Some((enemy, enemy_chance)) = &enemy else {skipped += 1; continue};
+				let Ok(reward) = Reward::try_from(cells.as_slice()) else {skipped += 1; continue};
+				drops.push(EnemyDrop{kind, enemy: enemy.clone(), enemy_chance: *enemy_chance, reward});
+			}
+		}
+	}
+	(drops, skipped)
+}
+
+/// Logs how many rows of a section were dropped
+fn report<T>(section: &str, (rows, skipped): Section<T>) -> Vec<T>
+{
+	if skipped > 0
+	{
+		eprintln!("Skipped {skipped} unparsable rows in droptable section {section}");
+	}
+	rows
+}
+
+pub fn load(file_path: &Path) -> Result<Droptable>
 {
 	if !file_path.exists()
 	{
-		let table = crate::live::droptable()?;
-		std::fs::write(file_path, table)?;
+		crate::live::droptable(file_path)?;
 	}
 	let contents = std::fs::read_to_string(file_path)?;
-	let parsed = Html::parse_document(&contents);
-	let table_selector = Selector::parse(r#"#missionRewards~table"#)
-		.unwrap();
-	let table = parsed.select(&table_selector)
-		.next()
+	let document = Html::parse_document(&contents);
+
+	let mission_rewards = rows(&document, "missionRewards")
+		.map(|r|report("missionRewards", mission_rewards(r)))
 		.context("Could not find the mission rewards table")?;
-	let relic_selector = Selector::parse(r#"td"#).unwrap();
-	let relics = table.select(&relic_selector)
-		.flat_map(|e|e.text().next())
-		.filter(|&r|is_relic(r))
-		.map(|r|r.trim_end_matches(" (Radiant)"))
-		.map(|r|r.to_string())
+	let relic_rewards = rows(&document, "relicRewards")
+		.map(|r|report("relicRewards", relic_rewards(r)))
+		.unwrap_or_default();
+	let location_rewards = REWARD_SOURCES
+		.iter()
+		.flat_map(|&(section, source)|
+		{
+			rows(&document, section)
+				.map(|r|report(section, location_rewards(source, r)))
+				.unwrap_or_default()
+		})
 		.collect();
-	Ok(relics)
-}
\ No newline at end of file
+	let enemy_drops = ENEMY_DROPS
+		.iter()
+		.flat_map(|&(section, kind)|
+		{
+			rows(&document, section)
+				.map(|r|report(section, enemy_drops(kind, r)))
+				.unwrap_or_default()
+		})
+		.collect();
+
+	Ok(Droptable{mission_rewards, relic_rewards, location_rewards, enemy_drops})
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn section(id: &str, table: &str) -> Vec<Row>
+	{
+		let html = format!("<html><body><h3 id=\"{id}\">Section:</h3><table>{table}</table></body></html>");
+		rows(&Html::parse_document(&html), id).unwrap()
+	}
+
+	const BLANK: &str = "<tr class=\"blank-row\"><td class=\"blank-row\" colspan=\"2\"></td></tr>";
+
+	#[test]
+	fn parses_percentages()
+	{
+		assert_eq!(parse_percent("25.00%").unwrap(), 25.0);
+		assert_eq!(parse_percent(" 3.5 ").unwrap(), 3.5);
+		assert!(parse_percent("many%").is_err());
+	}
+
+	#[test]
+	fn parses_locations()
+	{
+		assert_eq!(
+			parse_location("Mercury/Apollodorus (Survival)"),
+			("Mercury".into(), "Apollodorus".into(), "Survival".into()));
+		assert_eq!(
+			parse_location("Void/Mot (Survival) (Extra)"),
+			("Void".into(), "Mot (Survival)".into(), "Extra".into()));
+		assert_eq!(parse_location("Lua"), ("".into(), "Lua".into(), "".into()));
+	}
+
+	#[test]
+	fn parses_mission_rewards()
+	{
+		let rows = section("missionRewards", &format!("
+			<tr><th colspan=\"2\">Mercury/Apollodorus (Survival)</th></tr>
+			<tr><th colspan=\"2\">Rotation A</th></tr>
+			<tr><td>Lith C7 Relic</td><td>Uncommon (25.00%)</td></tr>
+			<tr><td>Broken Row</td><td>Sometimes</td></tr>
+			<tr><th colspan=\"2\">Rotation B</th></tr>
+			<tr><td>2X Orokin Cell</td><td>Rare (5.64%)</td></tr>
+			{BLANK}
+			<tr><th colspan=\"2\">Earth/Mantle (Capture)</th></tr>
+			<tr><td>Endo</td><td>Very Common (50.00%)</td></tr>"));
+		let (rewards, skipped) = mission_rewards(rows);
+		assert_eq!(skipped, 1);
+		assert_eq!(rewards.len(), 3);
+		assert_eq!(rewards[0].node, "Apollodorus");
+		assert_eq!(rewards[0].rotation, Some(Rotation::A));
+		assert_eq!(rewards[0].reward.rarity, DropRarity::Uncommon);
+		assert_eq!(rewards[1].rotation, Some(Rotation::B));
+		assert_eq!(rewards[1].reward.item, "2X Orokin Cell");
+		assert_eq!(rewards[1].reward.chance, 5.64);
+		assert_eq!(
+			(rewards[2].planet.as_str(), rewards[2].mission_type.as_str(), rewards[2].rotation),
+			("Earth", "Capture", None));
+	}
+
+	#[test]
+	fn parses_relic_rewards()
+	{
+		let rows = section("relicRewards", &format!("
+			<tr><td>Orphaned Item</td><td>Rare (2.00%)</td></tr>
+			<tr><th colspan=\"2\">Lith C7 Relic (Intact)</th></tr>
+			<tr><td>Forma Blueprint</td><td>Common (25.33%)</td></tr>
+			{BLANK}
+			<tr><th colspan=\"2\">Lith C7 Relic (Radiant)</th></tr>
+			<tr><td>Braton Prime Barrel</td><td>Rare (10.00%)</td></tr>
+			<tr><th colspan=\"2\">Lith C7 Relic (Shiny)</th></tr>
+			<tr><td>Forma Blueprint</td><td>Common (16.67%)</td></tr>"));
+		let (rewards, skipped) = relic_rewards(rows);
+		assert_eq!(skipped, 2);
+		assert_eq!(rewards.len(), 2);
+		assert_eq!(rewards[0].relic, "Lith C7 Relic");
+		assert_eq!(rewards[0].refinement, Refinement::Intact);
+		assert_eq!(rewards[1].refinement, Refinement::Radiant);
+		assert_eq!(rewards[1].reward.item, "Braton Prime Barrel");
+		assert_eq!(rewards[1].reward.chance, 10.0);
+	}
+
+	#[test]
+	fn parses_location_rewards()
+	{
+		let rows = section("cetusRewards", &format!("
+			<tr><th colspan=\"3\">Level 5 - 15 Cetus Bounty</th></tr>
+			<tr><th colspan=\"3\">Rotation A</th></tr>
+			<tr><th></th><th colspan=\"2\">Stage 1</th></tr>
+			<tr><td></td><td>Lith A1 Relic</td><td>Uncommon (14.29%)</td></tr>
+			<tr><td></td><td>Nothing</td><td>Unknown</td></tr>
+			{BLANK}
+			<tr><th colspan=\"3\">Rotation B</th></tr>
+			<tr><td></td><td>Endo</td><td>Common (28.57%)</td></tr>"));
+		let (rewards, skipped) = location_rewards(RewardSource::Cetus, rows);
+		assert_eq!(skipped, 1);
+		assert_eq!(rewards.len(), 2);
+		assert_eq!(rewards[0].source, RewardSource::Cetus);
+		assert_eq!(rewards[0].location, "Level 5 - 15 Cetus Bounty");
+		assert_eq!(rewards[0].rotation, Some(Rotation::A));
+		assert_eq!(rewards[0].stage.as_deref(), Some("Stage 1"));
+		assert_eq!(rewards[0].reward.item, "Lith A1 Relic");
+		assert_eq!(rewards[1].rotation, Some(Rotation::B));
+		assert_eq!(rewards[1].stage, None);
+	}
+
+	#[test]
+	fn parses_enemy_drops()
+	{
+		let rows = section("modByAvatar", &format!("
+			<tr><th>Arid Eviscerator</th><th>Mod Drop Chance: 3.00%</th></tr>
+			<tr><td></td><td>Heavy Caliber</td><td>Rare (0.50%)</td></tr>
+			{BLANK}
+			<tr><th>Unknown Enemy</th><th>Mod Drop Chance: lots</th></tr>
+			<tr><td></td><td>Serration</td><td>Uncommon (5.00%)</td></tr>"));
+		let (drops, skipped) = enemy_drops(EnemyDropKind::Mod, rows);
+		assert_eq!(skipped, 1);
+		assert_eq!(drops.len(), 1);
+		assert_eq!(drops[0].kind, EnemyDropKind::Mod);
+		assert_eq!(drops[0].enemy, "Arid Eviscerator");
+		assert_eq!(drops[0].enemy_chance, 3.0);
+		assert_eq!(drops[0].reward.item, "Heavy Caliber");
+		assert_eq!(drops[0].reward.rarity, DropRarity::Rare);
+	}
+}
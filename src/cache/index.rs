@@ -13,7 +13,7 @@ pub fn load(path: &Path) -> Result<HashMap<String, String>>
 	parse(&mut reader)
 }
 
-pub fn parse<R>(reader: &mut R) -> Result<HashMap<String, String>>
+fn parse<R>(reader: &mut R) -> Result<HashMap<String, String>>
 where
 	R: BufRead
 {
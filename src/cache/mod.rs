@@ -2,6 +2,7 @@ mod worldstate;
 mod index;
 pub mod recipes;
 mod relics;
+mod regions;
 mod resources;
 mod warframes;
 mod weapons;
@@ -9,17 +10,24 @@ mod manifest;
 mod state;
 mod droptable;
 mod sentinels;
+mod upgrades;
+mod other;
 
-pub use droptable::active_relics;
-pub use worldstate::resurgence_relics;
+pub use droptable::load as load_droptable;
+pub use worldstate::resurgence;
 pub use worldstate::invasions;
+pub use worldstate::fissures;
+pub use worldstate::void_traders;
 pub use index::load as load_index;
-pub use index::parse as parse_index;
 pub use recipes::load as load_recipes;
 pub use relics::load as load_relics;
+pub use regions::load as load_regions;
 pub use resources::load as load_resources;
 pub use warframes::load as load_warframes;
 pub use weapons::load as load_weapons;
 pub use state::load as load_state;
 pub use state::save as save_state;
-pub use sentinels::load as load_sentinels;
\ No newline at end of file
+pub use state::State;
+pub use sentinels::load as load_sentinels;
+pub use upgrades::load as load_upgrades;
+pub use other::load as load_other;
\ No newline at end of file
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use std::path::Path;
+use anyhow::{Result, Context};
+use serde::Deserialize;
+use serde::de::IgnoredAny;
+use super::manifest;
+
+/// Manifests hold one or more top level lists, e.g. `ExportRailjackWeapons`.
+/// Anything that isn't a list of objects is ignored
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum List
+{
+	Items(Vec<Entry>),
+	Other(IgnoredAny)
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Entry
+{
+	unique_name: Option<String>,
+	name: Option<String>,
+	#[serde(default)]
+	product_category: String,
+}
+
+/// Any named item, from a manifest without a dedicated loader
+#[derive(Clone, Debug, Default)]
+pub struct Item
+{
+	pub unique_name: String,
+	pub name: String,
+	pub product_category: String,
+}
+
+pub fn load(cache: &Path, manifest: &str) -> Result<Vec<Item>>
+{
+	let file = manifest::load(cache, manifest)
+		.context("Loading manifest")?;
+	let export: HashMap<String, List> = serde_json::from_str(&file)
+		.context("Parsing manifest")?;
+	let items = export.into_values()
+		.flat_map(|list|match list
+		{
+			List::Items(entries)=>entries,
+			List::Other(_)=>vec![]
+		})
+		.flat_map(|e|Some(Item
+		{
+			unique_name: e.unique_name?,
+			name: e.name?,
+			product_category: e.product_category
+		}))
+		.collect();
+	Ok(items)
+}
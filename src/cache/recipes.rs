@@ -17,7 +17,32 @@ pub struct Recipe
 {
 	pub unique_name: String,
 	pub result_type: String,
-	pub ingredients: Vec<Ingredient>
+	pub ingredients: Vec<Ingredient>,
+	#[serde(default)]
+	pub build_price: u32,
+	/// Seconds
+	#[serde(default)]
+	pub build_time: u32,
+	/// Platinum
+	#[serde(default)]
+	pub skip_build_time_price: u32,
+	#[serde(default = "default_num")]
+	pub num: u32,
+	#[serde(default = "default_consume_on_build")]
+	pub consume_on_build: bool,
+	/// Ducats
+	#[serde(default)]
+	pub prime_selling_price: Option<u32>
+}
+
+fn default_num() -> u32
+{
+	1
+}
+
+fn default_consume_on_build() -> bool
+{
+	true
 }
 
 #[derive(Deserialize, Clone, Debug, Default)]
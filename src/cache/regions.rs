@@ -0,0 +1,31 @@
+use std::path::Path;
+use anyhow::{Result, Context};
+use serde::Deserialize;
+use super::manifest;
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "PascalCase")]
+struct Export
+{
+	export_regions: Vec<Region>,
+}
+
+/// A star chart node
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Region
+{
+	/// e.g. `SolNode26`, as used by the worldstate
+	pub unique_name: String,
+	pub name: String,
+	pub system_name: String,
+}
+
+pub fn load(cache: &Path, manifest: &str) -> Result<Vec<Region>>
+{
+	let file = manifest::load(cache, manifest)
+		.context("Loading manifest")?;
+	serde_json::from_str(&file)
+		.map(|e: Export|e.export_regions)
+		.context("Parsing manifest")
+}
@@ -2,6 +2,7 @@ use std::path::Path;
 
 use anyhow::{Result, Context, anyhow};
 use serde::Deserialize;
+use serde::de::IgnoredAny;
 
 use super::manifest;
 use crate::relic::Rarity;
@@ -19,7 +20,13 @@ struct RelicArcane
 {
 	pub unique_name: String,
 	pub name: String,
-	pub relic_rewards: Option<Vec<Reward>>
+	pub relic_rewards: Option<Vec<Reward>>,
+	/// e.g. `RARE`, only on arcanes
+	#[serde(default)]
+	pub rarity: Option<String>,
+	/// One entry per rank, only on arcanes
+	#[serde(default)]
+	pub level_stats: Vec<IgnoredAny>
 }
 
 #[derive(Deserialize, Clone, Debug, Default)]
@@ -53,6 +60,38 @@ impl TryFrom<RelicArcane> for Relic
 	}
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct Arcane
+{
+	pub unique_name: String,
+	pub name: String,
+	pub rarity: Option<String>,
+	pub max_rank: u32
+}
+
+impl TryFrom<RelicArcane> for Arcane
+{
+	type Error = anyhow::Error;
+
+	fn try_from(value: RelicArcane) -> Result<Self, Self::Error>
+	{
+		if value.relic_rewards.is_none() && !value.level_stats.is_empty()
+		{
+			Ok(Self
+			{
+				unique_name: value.unique_name,
+				name: value.name,
+				rarity: value.rarity,
+				max_rank: value.level_stats.len() as u32 - 1
+			})
+		}
+		else
+		{
+			Err(anyhow!("RelicArcane {} was not an arcane", value.name))
+		}
+	}
+}
+
 #[derive(Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Reward
@@ -61,14 +100,21 @@ pub struct Reward
 	pub rarity: Rarity,
 }
 
-pub fn load(cache: &Path, manifest: &str) -> Result<Vec<Relic>>
+/// (Relics, Arcanes)
+pub fn load(cache: &Path, manifest: &str) -> Result<(Vec<Relic>, Vec<Arcane>)>
 {
 	let file = manifest::load(cache, manifest)
 		.context("Loading manifest")?;
 	let parsed: Export = serde_json::from_str(&file)
 		.context("Parsing manifest")?;
-	let relics = parsed.export_relic_arcane.into_iter()
+	let (relics, others): (Vec<_>, Vec<_>) = parsed.export_relic_arcane
+		.into_iter()
+		.partition(|r|r.relic_rewards.is_some());
+	let relics = relics.into_iter()
+		.flat_map(|r|r.try_into())
+		.collect();
+	let arcanes = others.into_iter()
 		.flat_map(|r|r.try_into())
 		.collect();
-	Ok(relics)
+	Ok((relics, arcanes))
 }
\ No newline at end of file
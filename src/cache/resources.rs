@@ -16,6 +16,9 @@ pub struct Resource
 {
     pub unique_name: String,
     pub name: String,
+    /// Ducats
+    #[serde(default)]
+    pub prime_selling_price: Option<u32>,
 }
 
 pub fn load(cache: &Path, manifest: &str) -> Result<Vec<Resource>>
@@ -16,6 +16,8 @@ pub struct Sentinel
 {
 	pub unique_name: String,
 	pub name: String,
+	#[serde(default)]
+	pub product_category: String,
 }
 
 pub fn load(cache: &Path, manifest: &str) -> Result<Vec<Sentinel>>
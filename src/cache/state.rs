@@ -5,17 +5,33 @@ use anyhow::{Result, Context};
 use serde::{Deserialize, Serialize};
 
 use crate::{Data, structures::UniqueName};
+use crate::foundry::Build;
+use crate::relic::RelicInventory;
 
 #[derive(Eq, PartialEq, Clone, Default, Deserialize, Serialize, Debug)]
 struct Saved
 {
 	tracked: Vec<UniqueName>,
 	owned: HashMap<UniqueName, u32>,
+	#[serde(default)]
+	foundry: Vec<Build>,
+	#[serde(default)]
+	relics: RelicInventory,
+}
+
+/// Everything the user has entered, kept between runs
+#[derive(Default)]
+pub struct State
+{
+	pub tracked: Vec<crate::Tracked>,
+	pub owned: HashMap<UniqueName, u32>,
+	pub foundry: Vec<Build>,
+	pub relics: RelicInventory
 }
 
 pub fn load(
 	tracked_path: &Path,
-	db: &mut Data) -> Result<(Vec<crate::Tracked>, HashMap<UniqueName, u32>)>
+	db: &mut Data) -> Result<State>
 {
 	let contents = std::fs::read_to_string(tracked_path)
 		.context("Loading tracked file from fs")?;
@@ -28,21 +44,37 @@ pub fn load(
 			.with_context(||format!("Enriching {tracked}"))?;
 		enriched.push(t);
 	}
-	Ok((enriched, parsed.owned))
+	Ok(State
+	{
+		tracked: enriched,
+		owned: parsed.owned,
+		foundry: parsed.foundry,
+		relics: parsed.relics
+	})
 }
 
 pub fn save(
 	tracked_path: &Path,
-	tracked: Vec<crate::Tracked>,
-	owned: HashMap<UniqueName, u32>) -> Result<()>
+	state: State) -> Result<()>
 {
+	let State{tracked, owned, foundry, relics} = state;
 	let tracked: Vec<_> = tracked.into_iter()
 		.map(|t|t.unique_name)
 		.collect();
 	let owned = owned.into_iter()
 		.filter(|&(_, v)|v!=0)
 		.collect();
-	let saved = Saved {tracked, owned};
+	let relics = relics.into_iter()
+		.map(|(relic, counts)|
+		{
+			let counts: HashMap<_, _> = counts.into_iter()
+				.filter(|&(_, v)|v!=0)
+				.collect();
+			(relic, counts)
+		})
+		.filter(|(_, counts)|!counts.is_empty())
+		.collect();
+	let saved = Saved {tracked, owned, foundry, relics};
 	let file = std::fs::File::create(tracked_path)
 		.context("Creating tracked file")?;
 	let mut buf = io::BufWriter::new(file);
@@ -0,0 +1,41 @@
+use std::path::Path;
+use anyhow::{Result, Context};
+use serde::Deserialize;
+use super::manifest;
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "PascalCase")]
+struct Export
+{
+	export_upgrades: Vec<Upgrade>,
+	/// Railjack mods
+	#[serde(default)]
+	export_avionics: Vec<Upgrade>,
+}
+
+/// A mod
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Upgrade
+{
+	pub unique_name: String,
+	pub name: String,
+	/// e.g. `AP_ATTACK`
+	#[serde(default)]
+	pub polarity: Option<String>,
+	/// e.g. `RARE`
+	#[serde(default)]
+	pub rarity: Option<String>,
+	/// Max rank
+	#[serde(default)]
+	pub fusion_limit: u32,
+}
+
+pub fn load(cache: &Path, manifest: &str) -> Result<Vec<Upgrade>>
+{
+	let file = manifest::load(cache, manifest)
+		.context("Loading manifest")?;
+	serde_json::from_str(&file)
+		.map(|e: Export|e.export_upgrades.into_iter().chain(e.export_avionics).collect())
+		.context("Parsing manifest")
+}
@@ -17,7 +17,8 @@ pub struct Warframe
 {
 	pub unique_name: String,
 	pub name: String,
-	// pub product_category: String
+	#[serde(default)]
+	pub product_category: String,
 }
 
 pub fn load(cache: &Path, manifest: &str) -> Result<Vec<Warframe>>
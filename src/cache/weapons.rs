@@ -8,6 +8,8 @@ use super::manifest;
 pub struct Export
 {
 	export_weapons: Vec<Weapon>,
+	#[serde(default)]
+	export_railjack_weapons: Vec<Weapon>,
 }
 
 #[derive(Deserialize, Clone, Debug, Default)]
@@ -16,6 +18,11 @@ pub struct Weapon
 {
 	pub unique_name: String,
 	pub name: String,
+	#[serde(default)]
+	pub product_category: String,
+	/// Ducats
+	#[serde(default)]
+	pub prime_selling_price: Option<u32>,
 }
 
 pub fn load(cache: &Path, manifest: &str) -> Result<Vec<Weapon>>
@@ -23,6 +30,6 @@ pub fn load(cache: &Path, manifest: &str) -> Result<Vec<Weapon>>
 	let file = manifest::load(cache, manifest)
 		.context("Loading manifest")?;
 	serde_json::from_str(&file)
-		.map(|e: Export|e.export_weapons)
+		.map(|e: Export|e.export_weapons.into_iter().chain(e.export_railjack_weapons).collect())
 		.context("Parsing manifest")
 }
\ No newline at end of file
@@ -1,27 +1,90 @@
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
+use std::time::{Duration, SystemTime};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::Deserialize;
 
+use crate::invasion::Faction;
+use crate::relic::RelicTier;
+
+const FISSURE_TIERS: [(&str, RelicTier); 6] = [
+	("VoidT1", RelicTier::Lith),
+	("VoidT2", RelicTier::Meso),
+	("VoidT3", RelicTier::Neo),
+	("VoidT4", RelicTier::Axi),
+	("VoidT5", RelicTier::Requiem),
+	("VoidT6", RelicTier::Omnia)];
+
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct State
 {
 	invasions: Vec<Invasion>,
-	prime_vault_traders: Vec<PrimeVaultTrader>
+	prime_vault_traders: Vec<PrimeVaultTrader>,
+	#[serde(default)]
+	active_missions: Vec<ActiveMission>,
+	#[serde(default)]
+	void_traders: Vec<VoidTrader>
+}
+
+/// `{"$date": {"$numberLong": "1700000000000"}}`
+#[derive(Deserialize, Debug)]
+#[serde(try_from = "MongoDate")]
+struct Date(SystemTime);
+
+#[derive(Deserialize, Debug)]
+struct MongoDate
+{
+	#[serde(rename = "$date")]
+	date: NumberLong
+}
+
+#[derive(Deserialize, Debug)]
+struct NumberLong
+{
+	#[serde(rename = "$numberLong")]
+	number_long: String
+}
+
+impl TryFrom<MongoDate> for Date
+{
+	type Error = anyhow::Error;
+
+	fn try_from(value: MongoDate) -> Result<Self, Self::Error>
+	{
+		let millis = value.date.number_long.parse()
+			.with_context(||format!("Unexpected timestamp: {}", value.date.number_long))?;
+		Ok(Self(SystemTime::UNIX_EPOCH + Duration::from_millis(millis)))
+	}
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
 struct Invasion
 {
-	_faction: InvasionFaction,
+	node: String,
+	/// Attacking faction
+	faction: Faction,
+	defender_faction: Option<Faction>,
+	/// The faction fought when supporting the attacker
+	attacker_mission_info: Option<MissionInfo>,
+	/// Positive when the attacker is ahead
+	count: i64,
+	goal: i64,
+	#[serde(default)]
+	completed: bool,
 	attacker_reward: RewardOrEmptyVec,
 	defender_reward: RewardOrEmptyVec
 }
 
+#[derive(Deserialize, Debug)]
+struct MissionInfo
+{
+	faction: Faction
+}
+
 #[derive(Deserialize, Debug)]
 #[serde(untagged)]
 enum RewardOrEmptyVec
@@ -42,17 +105,6 @@ impl RewardOrEmptyVec
 	}
 }
 
-#[derive(Deserialize, Debug)]
-enum InvasionFaction
-{
-	#[serde(rename = "FC_GRINEER")]
-	Grineer,
-	#[serde(rename = "FC_CORPUS")]
-	Corpus,
-	#[serde(rename = "FC_INFESTATION")]
-	Infested
-}
-
 #[derive(Deserialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 struct Reward
@@ -65,67 +117,462 @@ struct Reward
 struct CountedItem
 {
 	item_type: String,
-	_item_count: usize
+	item_count: u32
 }
 
-pub fn invasions(file_path: &Path) -> Result<Vec<String>>
+impl Reward
+{
+	fn into_items(self) -> Vec<(String, u32)>
+	{
+		self.counted_items
+			.into_iter()
+			.map(|i|(normalize_store_item(&i.item_type), i.item_count))
+			.collect()
+	}
+}
+
+/// An invasion that is still running
+#[derive(Clone, Debug)]
+pub struct ActiveInvasion
+{
+	/// Node id, e.g. `SolNode26`
+	pub node: String,
+	pub attacker: Faction,
+	pub defender: Faction,
+	/// Rewards for supporting the attacker, with their counts
+	pub attacker_rewards: Vec<(String, u32)>,
+	pub defender_rewards: Vec<(String, u32)>,
+	/// Percent of the way to the attacker winning
+	pub attacker_progress: f32
+}
+
+pub fn invasions(file_path: &Path) -> Result<Vec<ActiveInvasion>>
 {
 	if !file_path.exists()
 	{
-		let worldstate = crate::live::worldstate()?;
-		std::fs::write(file_path, worldstate)?;
+		crate::live::worldstate(file_path)?;
 	}
 
 	let reader = BufReader::new(File::open(file_path)?);
 	let world_state: State = serde_json::from_reader(reader)?;
-	let faction_rewards = world_state.invasions.into_iter()
+	let invasions = world_state.invasions.into_iter()
+		.filter(|invasion|!invasion.completed)
 		.flat_map(|invasion|
 		{
-			let attacker_rewards = invasion.attacker_reward.into_reward()
-				.counted_items;
-			let defender_rewards = invasion.defender_reward.into_reward()
-				.counted_items;
-			attacker_rewards.into_iter().chain(defender_rewards)
-		});
-	let rewards = faction_rewards.map(|i|i.item_type).collect();
-	Ok(rewards)
+			let defender = invasion.defender_faction
+				.or(invasion.attacker_mission_info.map(|i|i.faction))?;
+			// Infested invasions only run from the attacker's lead down to zero
+			let scale = if invasion.faction == Faction::Infested {100.0} else {50.0};
+			let attacker_progress = (1.0 + invasion.count as f32 / invasion.goal.max(1) as f32) * scale;
+			Some(ActiveInvasion
+			{
+				node: invasion.node,
+				attacker: invasion.faction,
+				defender,
+				attacker_rewards: invasion.attacker_reward.into_reward().into_items(),
+				defender_rewards: invasion.defender_reward.into_reward().into_items(),
+				attacker_progress: attacker_progress.clamp(0.0, 100.0)
+			})
+		})
+		.collect();
+	Ok(invasions)
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
-pub struct PrimeVaultTrader
+struct PrimeVaultTrader
 {
-	pub manifest: Vec<Item>
+	activation: Date,
+	expiry: Date,
+	manifest: Vec<Item>,
+	#[serde(default)]
+	schedule_info: Vec<Schedule>
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
-pub struct Item
+struct Item
 {
-	pub item_type: String,
+	item_type: String,
+	/// Aya
+	prime_price: Option<u32>,
+	/// Regal Aya
+	regular_price: Option<u32>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct Schedule
+{
+	expiry: Date,
+	featured_item: Option<String>
+}
+
+/// Something sold by Varzia during Prime Resurgence
+#[derive(Clone, Debug)]
+pub struct VaultOffer
+{
+	pub item: String,
+	pub aya: Option<u32>,
+	pub regal_aya: Option<u32>,
+	pub activation: SystemTime,
+	pub expiry: SystemTime
+}
+
+/// A featured item of a Prime Resurgence rotation
+#[derive(Clone, Debug)]
+pub struct ScheduledRotation
+{
+	pub item: String,
+	pub starts: SystemTime,
+	pub expiry: SystemTime
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Resurgence
+{
+	pub offers: Vec<VaultOffer>,
+	/// Oldest rotation first
+	pub schedule: Vec<ScheduledRotation>
+}
+
+/// Resurgence store items contain a "StoreItems" node in the path which
+/// needs to be removed to make it line up with the rest of the names.
+// TODO: I feel like there should be a better way to do this.
+fn normalize_store_item(item: &str) -> String
+{
+	item.split('/')
+		.filter(|&s|s != "StoreItems")
+		.collect::<Vec<_>>()
+		.join("/")
 }
 
-pub fn resurgence_relics(file_path: &Path) -> Result<Vec<String>>
+pub fn resurgence(file_path: &Path) -> Result<Resurgence>
 {
 	if !file_path.exists()
 	{
-		let worldstate = crate::live::worldstate()?;
-		std::fs::write(file_path, worldstate)?;
+		crate::live::worldstate(file_path)?;
 	}
 	let reader = File::open(file_path)
 		.map(BufReader::new)?;
 	let world_state: State = serde_json::from_reader(reader)?;
-	let store_items = world_state.prime_vault_traders.iter()
-		.flat_map(|t|&t.manifest)
-		.map(|i|&i.item_type[..])
-		.filter(|i|i.starts_with("/Lotus/StoreItems/Types/Game/Projections/"));
-	// Resurgence store items contain a "StoreItems" node in the path which
-	// needs to be removed to make it line up with the rest of the names.
-	// TODO: I feel like there should be a better way to do this.
-	let normalized_store_items = store_items
-		.map(|i|i.split('/').filter(|&s|s != "StoreItems").collect());
-	let relics = normalized_store_items
-		.map(|i: Vec<_>|i.join("/"))
+
+	let mut resurgence = Resurgence::default();
+	for trader in world_state.prime_vault_traders
+	{
+		let (activation, expiry) = (trader.activation.0, trader.expiry.0);
+		resurgence.offers.extend(trader.manifest
+			.into_iter()
+			.map(|i|VaultOffer
+			{
+				item: normalize_store_item(&i.item_type),
+				aya: i.prime_price,
+				regal_aya: i.regular_price,
+				activation,
+				expiry
+			}));
+
+		// Each rotation starts when the previous one expires
+		let mut schedule = trader.schedule_info;
+		schedule.sort_by_key(|s|s.expiry.0);
+		let mut starts = activation;
+		for rotation in schedule
+		{
+			if let Some(item) = rotation.featured_item
+			{
+				resurgence.schedule.push(ScheduledRotation
+				{
+					item: normalize_store_item(&item),
+					starts,
+					expiry: rotation.expiry.0
+				});
+			}
+			starts = rotation.expiry.0;
+		}
+	}
+	resurgence.schedule.sort_by_key(|r|r.starts);
+	Ok(resurgence)
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct ActiveMission
+{
+	node: String,
+	mission_type: String,
+	modifier: String,
+	expiry: Date,
+	/// Steel Path
+	#[serde(default)]
+	hard: bool
+}
+
+/// A void fissure
+#[derive(Clone, Debug)]
+pub struct Fissure
+{
+	/// Node id, e.g. `SolNode26`
+	pub node: String,
+	pub mission_type: String,
+	pub tier: RelicTier,
+	pub expiry: SystemTime,
+	pub steel_path: bool
+}
+
+/// `MT_MOBILE_DEFENSE` to `Mobile Defense`
+fn mission_type_name(mission_type: &str) -> String
+{
+	mission_type.trim_start_matches("MT_")
+		.split('_')
+		.map(|word|
+		{
+			let word = word.to_ascii_lowercase();
+			let mut chars = word.chars();
+			chars.next()
+				.map(|c|c.to_ascii_uppercase().to_string() + chars.as_str())
+				.unwrap_or_default()
+		})
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+pub fn fissures(file_path: &Path) -> Result<Vec<Fissure>>
+{
+	if !file_path.exists()
+	{
+		crate::live::worldstate(file_path)?;
+	}
+	let reader = File::open(file_path)
+		.map(BufReader::new)?;
+	let world_state: State = serde_json::from_reader(reader)?;
+	let fissures = world_state.active_missions.into_iter()
+		.flat_map(|mission|
+		{
+			let tier = FISSURE_TIERS.iter()
+				.find(|&&(modifier, _)|modifier == mission.modifier)
+				.map(|&(_, tier)|tier)?;
+			Some(Fissure
+			{
+				mission_type: mission_type_name(&mission.mission_type),
+				node: mission.node,
+				tier,
+				expiry: mission.expiry.0,
+				steel_path: mission.hard
+			})
+		})
 		.collect();
-	Ok(relics)
+	Ok(fissures)
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct VoidTrader
+{
+	character: String,
+	node: String,
+	activation: Date,
+	expiry: Date,
+	/// Only sent while the trader is at the relay
+	#[serde(default)]
+	manifest: Vec<Item>
+}
+
+/// A visit from Baro Ki'Teer
+#[derive(Clone, Debug)]
+pub struct VoidTraderVisit
+{
+	pub character: String,
+	/// Relay id, e.g. `PlutoHUB`
+	pub node: String,
+	pub activation: SystemTime,
+	pub expiry: SystemTime,
+	pub items: Vec<VoidTraderItem>
+}
+
+#[derive(Clone, Debug)]
+pub struct VoidTraderItem
+{
+	pub item: String,
+	pub ducats: Option<u32>,
+	pub credits: Option<u32>
+}
+
+pub fn void_traders(file_path: &Path) -> Result<Vec<VoidTraderVisit>>
+{
+	if !file_path.exists()
+	{
+		crate::live::worldstate(file_path)?;
+	}
+	let reader = File::open(file_path)
+		.map(BufReader::new)?;
+	let world_state: State = serde_json::from_reader(reader)?;
+	let visits = world_state.void_traders.into_iter()
+		.map(|trader|VoidTraderVisit
+		{
+			character: trader.character,
+			node: trader.node,
+			activation: trader.activation.0,
+			expiry: trader.expiry.0,
+			items: trader.manifest
+				.into_iter()
+				.map(|i|VoidTraderItem
+				{
+					item: normalize_store_item(&i.item_type),
+					ducats: i.prime_price,
+					credits: i.regular_price
+				})
+				.collect()
+		})
+		.collect();
+	Ok(visits)
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::path::PathBuf;
+
+	use super::*;
+
+	fn date(millis: u64) -> String
+	{
+		format!(r#"{{"$date": {{"$numberLong": "{millis}"}}}}"#)
+	}
+
+	fn at(millis: u64) -> SystemTime
+	{
+		SystemTime::UNIX_EPOCH + Duration::from_millis(millis)
+	}
+
+	fn write(name: &str, json: &str) -> PathBuf
+	{
+		let path = std::env::temp_dir().join(format!("worldstate_test_{name}.json"));
+		std::fs::write(&path, json).unwrap();
+		path
+	}
+
+	fn invasion(node: &str, attacker: &str, count: i64, goal: i64, completed: bool) -> String
+	{
+		format!(r#"{{
+			"Node": "{node}",
+			"Faction": "{attacker}",
+			"DefenderFaction": "FC_CORPUS",
+			"Count": {count},
+			"Goal": {goal},
+			"Completed": {completed},
+			"AttackerReward": {{"countedItems": [{{"ItemType": "/Lotus/StoreItems/Types/Recipes/Weapons/KarakWraithBarrel", "ItemCount": 1}}]}},
+			"DefenderReward": []
+		}}"#)
+	}
+
+	#[test]
+	fn invasion_progress()
+	{
+		let invasions = [
+			// A quarter of the way from even towards the defender winning
+			invasion("SolNode1", "FC_GRINEER", -2500, 10000, false),
+			// Infested start with a full lead that counts down
+			invasion("SolNode2", "FC_INFESTATION", -3000, 10000, false),
+			invasion("SolNode3", "FC_GRINEER", 20000, 10000, false),
+			invasion("SolNode4", "FC_GRINEER", 5, 0, false),
+			invasion("SolNode5", "FC_GRINEER", 0, 10000, true)];
+		let json = format!(r#"{{"Invasions": [{}], "PrimeVaultTraders": []}}"#, invasions.join(","));
+		let invasions = super::invasions(&write("invasions", &json)).unwrap();
+
+		let progress: Vec<_> = invasions.iter()
+			.map(|i|(i.node.as_str(), i.attacker_progress))
+			.collect();
+		assert_eq!(progress, [("SolNode1", 37.5), ("SolNode2", 70.0), ("SolNode3", 100.0), ("SolNode4", 100.0)]);
+		assert_eq!(invasions[1].attacker, Faction::Infested);
+		assert_eq!(invasions[1].defender, Faction::Corpus);
+		assert_eq!(invasions[0].attacker_rewards, [("/Lotus/Types/Recipes/Weapons/KarakWraithBarrel".to_owned(), 1)]);
+		assert!(invasions[0].defender_rewards.is_empty());
+	}
+
+	#[test]
+	fn invasion_defender_from_mission_info()
+	{
+		let json = r#"{"Invasions": [{
+			"Node": "SolNode1",
+			"Faction": "FC_GRINEER",
+			"AttackerMissionInfo": {"faction": "FC_INFESTATION"},
+			"Count": 0,
+			"Goal": 10000,
+			"AttackerReward": [],
+			"DefenderReward": {"countedItems": [{"ItemType": "/Lotus/StoreItems/Types/Items/MiscItems/Alertium", "ItemCount": 3}]}
+		}], "PrimeVaultTraders": []}"#;
+		let invasions = super::invasions(&write("invasion_mission_info", json)).unwrap();
+		assert_eq!(invasions[0].defender, Faction::Infested);
+		assert_eq!(invasions[0].attacker_progress, 50.0);
+		assert_eq!(invasions[0].defender_rewards, [("/Lotus/Types/Items/MiscItems/Alertium".to_owned(), 3)]);
+	}
+
+	#[test]
+	fn resurgence_rotations_start_when_the_previous_one_ends()
+	{
+		let json = format!(r#"{{"Invasions": [], "PrimeVaultTraders": [{{
+			"Activation": {},
+			"Expiry": {},
+			"Manifest": [{{"ItemType": "/Lotus/StoreItems/Types/Game/Projections/T1VoidProjectionA", "PrimePrice": 1, "RegularPrice": 2}}],
+			"ScheduleInfo": [
+				{{"Expiry": {}, "FeaturedItem": "/Lotus/StoreItems/Types/Game/Third"}},
+				{{"Expiry": {}, "FeaturedItem": "/Lotus/StoreItems/Types/Game/First"}},
+				{{"Expiry": {}}}
+			]
+		}}]}}"#, date(1000), date(9000), date(4000), date(2000), date(3000));
+		let resurgence = super::resurgence(&write("resurgence", &json)).unwrap();
+
+		let offer = &resurgence.offers[0];
+		assert_eq!(offer.item, "/Lotus/Types/Game/Projections/T1VoidProjectionA");
+		assert_eq!((offer.aya, offer.regal_aya), (Some(1), Some(2)));
+		assert_eq!((offer.activation, offer.expiry), (at(1000), at(9000)));
+
+		// The rotation without a featured item still moves the next start on
+		let schedule: Vec<_> = resurgence.schedule.iter()
+			.map(|r|(r.item.as_str(), r.starts, r.expiry))
+			.collect();
+		assert_eq!(schedule, [
+			("/Lotus/Types/Game/First", at(1000), at(2000)),
+			("/Lotus/Types/Game/Third", at(3000), at(4000))]);
+	}
+
+	#[test]
+	fn void_trader_items()
+	{
+		let json = format!(r#"{{"Invasions": [], "PrimeVaultTraders": [], "VoidTraders": [
+			{{
+				"Character": "Baro'Ki Teel",
+				"Node": "PlutoHUB",
+				"Activation": {},
+				"Expiry": {},
+				"Manifest": [{{"ItemType": "/Lotus/StoreItems/Upgrades/Mods/Rifle/Primed", "PrimePrice": 350, "RegularPrice": 110000}}]
+			}},
+			{{"Character": "Baro'Ki Teel", "Node": "EarthHUB", "Activation": {}, "Expiry": {}}}
+		]}}"#, date(1000), date(2000), date(3000), date(4000));
+		let visits = super::void_traders(&write("void_traders", &json)).unwrap();
+
+		assert_eq!(visits.len(), 2);
+		assert_eq!(visits[0].node, "PlutoHUB");
+		assert_eq!((visits[0].activation, visits[0].expiry), (at(1000), at(2000)));
+		let item = &visits[0].items[0];
+		assert_eq!(item.item, "/Lotus/Upgrades/Mods/Rifle/Primed");
+		assert_eq!((item.ducats, item.credits), (Some(350), Some(110000)));
+		// Upcoming visits don't list their items yet
+		assert!(visits[1].items.is_empty());
+	}
+
+	#[test]
+	fn fissure_tiers()
+	{
+		let json = format!(r#"{{"Invasions": [], "PrimeVaultTraders": [], "ActiveMissions": [
+			{{"Node": "SolNode1", "MissionType": "MT_MOBILE_DEFENSE", "Modifier": "VoidT2", "Expiry": {}, "Hard": true}},
+			{{"Node": "SolNode2", "MissionType": "MT_SURVIVAL", "Modifier": "VoidT9", "Expiry": {}}}
+		]}}"#, date(1000), date(2000));
+		let fissures = super::fissures(&write("fissures", &json)).unwrap();
+
+		assert_eq!(fissures.len(), 1);
+		assert_eq!(fissures[0].tier, RelicTier::Meso);
+		assert_eq!(fissures[0].mission_type, "Mobile Defense");
+		assert!(fissures[0].steel_path);
+	}
 }
\ No newline at end of file
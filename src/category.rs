@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// What kind of item something is, from the manifest it is listed in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Category
+{
+	Warframe,
+	Primary,
+	Secondary,
+	Melee,
+	Sentinel,
+	Archwing,
+	CompanionWeapon,
+	Railjack,
+	Resource,
+	/// A part used to build something else, e.g. `Braton Prime Barrel`
+	Component,
+	Blueprint,
+	Mod,
+	Arcane,
+	/// Consumables and equipment, e.g. `Specters`
+	Gear,
+	Key,
+	Extractor,
+	/// Glyphs, skins and other cosmetics
+	Cosmetic
+}
+
+impl Category
+{
+	/// Category of a warframe, weapon or companion from its `productCategory`
+	pub fn from_product_category(product_category: &str) -> Option<Self>
+	{
+		match product_category
+		{
+			"Suits" | "MechSuits"=>Some(Self::Warframe),
+			"LongGuns"=>Some(Self::Primary),
+			"Pistols"=>Some(Self::Secondary),
+			"Melee"=>Some(Self::Melee),
+			"Sentinels" | "KubrowPets" | "MoaPets"=>Some(Self::Sentinel),
+			"SpaceSuits" | "SpaceGuns" | "SpaceMelee"=>Some(Self::Archwing),
+			"SentinelWeapons"=>Some(Self::CompanionWeapon),
+			"CrewShips" | "CrewShipWeapons"=>Some(Self::Railjack),
+			_=>None
+		}
+	}
+
+	/// Category of an entry in ExportResources. Parts are filed under recipes
+	pub fn of_resource(unique_name: &str) -> Self
+	{
+		if unique_name.contains("/Types/Recipes/")
+		{
+			Self::Component
+		}
+		else
+		{
+			Self::Resource
+		}
+	}
+}
+
+impl Display for Category
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			Self::Warframe=>"Warframe".fmt(f),
+			Self::Primary=>"Primary".fmt(f),
+			Self::Secondary=>"Secondary".fmt(f),
+			Self::Melee=>"Melee".fmt(f),
+			Self::Sentinel=>"Sentinel".fmt(f),
+			Self::Archwing=>"Archwing".fmt(f),
+			Self::CompanionWeapon=>"Companion Weapon".fmt(f),
+			Self::Railjack=>"Railjack".fmt(f),
+			Self::Resource=>"Resource".fmt(f),
+			Self::Component=>"Component".fmt(f),
+			Self::Blueprint=>"Blueprint".fmt(f),
+			Self::Mod=>"Mod".fmt(f),
+			Self::Arcane=>"Arcane".fmt(f),
+			Self::Gear=>"Gear".fmt(f),
+			Self::Key=>"Key".fmt(f),
+			Self::Extractor=>"Extractor".fmt(f),
+			Self::Cosmetic=>"Cosmetic".fmt(f)
+		}
+	}
+}
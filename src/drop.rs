@@ -0,0 +1,104 @@
+use std::fmt::Display;
+
+use anyhow::{anyhow, Result, Error};
+use serde::{Deserialize, Serialize};
+
+/// Rarity tier as printed in the droptable
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DropRarity
+{
+	VeryCommon,
+	Common,
+	Uncommon,
+	Rare,
+	UltraRare,
+	Legendary
+}
+
+impl TryFrom<&str> for DropRarity
+{
+	type Error = Error;
+	fn try_from(i: &str) -> Result<Self, Self::Error>
+	{
+		match i
+		{
+			"Very Common"=>Ok(Self::VeryCommon),
+			"Common"=>Ok(Self::Common),
+			"Uncommon"=>Ok(Self::Uncommon),
+			"Rare"=>Ok(Self::Rare),
+			"Ultra Rare"=>Ok(Self::UltraRare),
+			"Legendary"=>Ok(Self::Legendary),
+			_=>Err(anyhow!("Unknown drop rarity: {}", i))
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Rotation
+{
+	A,
+	B,
+	C
+}
+
+impl TryFrom<&str> for Rotation
+{
+	type Error = Error;
+	fn try_from(i: &str) -> Result<Self, Self::Error>
+	{
+		match i.trim_start_matches("Rotation ")
+		{
+			"A"=>Ok(Self::A),
+			"B"=>Ok(Self::B),
+			"C"=>Ok(Self::C),
+			_=>Err(anyhow!("Unknown rotation: {}", i))
+		}
+	}
+}
+
+impl Display for Rotation
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			Self::A=>"A".fmt(f),
+			Self::B=>"B".fmt(f),
+			Self::C=>"C".fmt(f)
+		}
+	}
+}
+
+/// Non-mission reward tables in the droptable
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RewardSource
+{
+	Key,
+	Transient,
+	Sortie,
+	Cetus,
+	OrbVallis,
+	CambionDrift,
+	Zariman,
+	EntratiLabs
+}
+
+/// Per-enemy drop tables in the droptable
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EnemyDropKind
+{
+	Mod,
+	Blueprint,
+	Resource
+}
+
+/// Somewhere an item can be farmed
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropSource
+{
+	/// Mission node, bounty or enemy
+	pub location: String,
+	pub rotation: Option<Rotation>,
+	/// Percent chance per rotation, bounty stage or kill
+	pub chance: f32
+}
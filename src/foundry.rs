@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+use crate::structures::{Data, UniqueName};
+
+/// A recipe currently being built in the foundry
+#[derive(Eq, PartialEq, Clone, Deserialize, Serialize, Debug)]
+pub struct Build
+{
+	pub recipe: UniqueName,
+	pub started: SystemTime,
+	pub build_time: Duration
+}
+
+impl Build
+{
+	pub fn new(db: &Data, recipe: UniqueName) -> Self
+	{
+		let build_time = db.recipe_build_info(recipe.clone())
+			.unwrap_or_default()
+			.build_time;
+		Self{recipe, started: SystemTime::now(), build_time}
+	}
+
+	pub fn remaining(&self) -> Duration
+	{
+		let elapsed = self.started.elapsed()
+			.unwrap_or_default();
+		self.build_time.saturating_sub(elapsed)
+	}
+
+	pub fn is_done(&self) -> bool
+	{
+		self.remaining().is_zero()
+	}
+}
+
+/// Remove finished builds from the foundry, adding their result to the owned
+/// counts and using up their ingredients
+pub fn collect_finished(
+	db: &Data,
+	foundry: &mut Vec<Build>,
+	owned: &mut HashMap<UniqueName, u32>)
+{
+	let (finished, building) = std::mem::take(foundry)
+		.into_iter()
+		.partition(Build::is_done);
+	*foundry = building;
+
+	for build in finished
+	{
+		let build_info = db.recipe_build_info(build.recipe.clone())
+			.unwrap_or_default();
+		if let Some(result) = db.recipe_result(build.recipe.clone())
+		{
+			*owned.entry(result).or_default() += build_info.quantity;
+		}
+
+		for (ingredient, count) in db.requirements(build.recipe.clone())
+		{
+			let owned = owned.entry(ingredient).or_default();
+			*owned = owned.saturating_sub(count.to_u32());
+		}
+
+		if build_info.consume_on_build
+		{
+			let owned = owned.entry(build.recipe).or_default();
+			*owned = owned.saturating_sub(1);
+		}
+	}
+}
@@ -0,0 +1,45 @@
+use std::fmt::Display;
+
+use serde::Deserialize;
+
+use crate::structures::CommonName;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum Faction
+{
+	#[serde(rename = "FC_GRINEER")]
+	Grineer,
+	#[serde(rename = "FC_CORPUS")]
+	Corpus,
+	#[serde(rename = "FC_INFESTATION")]
+	Infested
+}
+
+impl Display for Faction
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			Self::Grineer=>"Grineer".fmt(f),
+			Self::Corpus=>"Corpus".fmt(f),
+			Self::Infested=>"Infested".fmt(f)
+		}
+	}
+}
+
+/// An invasion rewarding a tracked item
+#[derive(Clone, Debug)]
+pub struct InvasionReward
+{
+	/// Node name with its system, e.g. `Apollodorus (Mercury)`
+	pub node: CommonName,
+	/// Faction to support to get the reward
+	pub ally: Faction,
+	/// Faction fought in the missions
+	pub enemy: Faction,
+	/// How many of the item a completed reward gives
+	pub count: u32,
+	/// Percent of the way to the supported side winning
+	pub progress: f32
+}
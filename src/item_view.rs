@@ -1,3 +1,5 @@
+use crate::drop::DropSource;
+use crate::invasion::InvasionReward;
 use crate::relic::Relic;
 use crate::structures::{CommonName, UniqueName};
 
@@ -7,5 +9,10 @@ pub trait ItemView
 	fn unique_name(&self) -> UniqueName;
 	fn resurgence_relics(&self) -> &[Relic];
 	fn active_relics(&self) -> &[Relic];
-	fn available_from_invasion(&self) -> bool;
+	/// Running invasions that reward this item
+	fn invasions(&self) -> &[InvasionReward];
+	/// Missions, bounties and enemies that drop this item, best first
+	fn drop_sources(&self) -> &[DropSource];
+	/// Recipe used to build this item in the foundry, if any
+	fn recipe_unique_name(&self) -> Option<UniqueName>;
 }
\ No newline at end of file
@@ -0,0 +1,98 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+
+/// Cache validators returned by the server for a downloaded file
+#[derive(Deserialize, Serialize, Default, Debug)]
+struct Validators
+{
+	etag: Option<String>,
+	last_modified: Option<String>
+}
+
+/// `path` with `suffix` added to its file name
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf
+{
+	let mut file_name = path.file_name()
+		.unwrap_or_default()
+		.to_owned();
+	file_name.push(suffix);
+	path.with_file_name(file_name)
+}
+
+fn validators_path(path: &Path) -> PathBuf
+{
+	sibling_path(path, ".validators.json")
+}
+
+/// Write to a temporary file next to `path` and move it into place, so an
+/// interrupted write never leaves a truncated file behind
+fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<()>
+{
+	let partial = sibling_path(path, ".partial");
+	std::fs::write(&partial, contents)
+		.with_context(||format!("Writing {}", partial.display()))?;
+	std::fs::rename(&partial, path)
+		.with_context(||format!("Moving {} into place", path.display()))
+}
+
+fn load_validators(path: &Path) -> Validators
+{
+	// Validators are meaningless without the file they describe
+	if !path.exists()
+	{
+		return Validators::default()
+	}
+	std::fs::read_to_string(validators_path(path))
+		.ok()
+		.and_then(|v|serde_json::from_str(&v).ok())
+		.unwrap_or_default()
+}
+
+/// Download `url` into `path`, unless the server reports the copy already
+/// there is current. Returns whether the file was rewritten
+pub fn fetch(url: &str, path: &Path) -> Result<bool>
+{
+	super::ensure_online()?;
+	let validators = load_validators(path);
+	let mut request = ureq::get(url);
+	if let Some(etag) = &validators.etag
+	{
+		request = request.header("If-None-Match", etag);
+	}
+	if let Some(last_modified) = &validators.last_modified
+	{
+		request = request.header("If-Modified-Since", last_modified);
+	}
+	let response = request.call()
+		.with_context(||format!("Sending GET request for {url}"))?;
+	if response.status() == 304
+	{
+		return Ok(false)
+	}
+
+	let header = |name|response.headers()
+		.get(name)
+		.and_then(|v|v.to_str().ok())
+		.map(|v|v.to_owned());
+	let validators = Validators
+	{
+		etag: header("ETag"),
+		last_modified: header("Last-Modified")
+	};
+
+	let mut payload = vec![];
+	response
+		.into_body()
+		.into_reader()
+		.read_to_end(&mut payload)
+		.with_context(||format!("Reading response payload from {url}"))?;
+	write_atomic(path, payload)?;
+
+	let validators = serde_json::to_string(&validators)?;
+	write_atomic(&validators_path(path), validators)
+		.context("Writing cache validators")?;
+	Ok(true)
+}
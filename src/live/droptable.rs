@@ -1,14 +1,10 @@
-use anyhow::{Result, Context};
+use std::path::Path;
 
-const DROPTABLE: &str = "https://www.warframe.com/droptables";
+use anyhow::{Result, Context};
 
-pub fn droptable() -> Result<String>
+/// Returns whether the droptable changed
+pub fn droptable(path: &Path) -> Result<bool>
 {
-	ureq::get(DROPTABLE)
-		.call()
-		.context("Sending GET request for the droptable")?
-		.into_body()
-		.read_to_string()
-		.context("Parsing response from droptable GET")
+	super::conditional::fetch(&super::endpoints().droptable, path)
+		.context("Downloading the droptable")
 }
-
@@ -0,0 +1,77 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{Result, Context};
+use serde::Deserialize;
+
+const CONFIG_FILE: &str = "endpoints.json";
+
+static ENDPOINTS: OnceLock<Endpoints> = OnceLock::new();
+
+/// Where each download comes from. Any of them can be set in `endpoints.json`
+/// in the cache dir, and then overridden by a `PRIME_<NAME>_URL` env var
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct Endpoints
+{
+	/// Base of the manifest index
+	pub export: String,
+	/// Base of the individual manifests
+	pub manifest: String,
+	pub worldstate: String,
+	pub droptable: String
+}
+
+impl Default for Endpoints
+{
+	fn default() -> Self
+	{
+		Self
+		{
+			export: "https://content.warframe.com/PublicExport".to_owned(),
+			manifest: "https://content.warframe.com/PublicExport/Manifest".to_owned(),
+			worldstate: "https://content.warframe.com/dynamic/worldState.php".to_owned(),
+			droptable: "https://www.warframe.com/droptables".to_owned()
+		}
+	}
+}
+
+impl Endpoints
+{
+	fn load(cache_dir: &Path) -> Result<Self>
+	{
+		let mut endpoints = match std::fs::read_to_string(cache_dir.join(CONFIG_FILE))
+		{
+			Ok(contents)=>serde_json::from_str(&contents)
+				.context("Parsing endpoint config")?,
+			Err(_)=>Self::default()
+		};
+
+		let overrides = [
+			("PRIME_EXPORT_URL", &mut endpoints.export),
+			("PRIME_MANIFEST_URL", &mut endpoints.manifest),
+			("PRIME_WORLDSTATE_URL", &mut endpoints.worldstate),
+			("PRIME_DROPTABLE_URL", &mut endpoints.droptable)];
+		for (var, endpoint) in overrides
+		{
+			if let Ok(url) = std::env::var(var)
+			{
+				*endpoint = url;
+			}
+		}
+		Ok(endpoints)
+	}
+}
+
+/// Read the endpoint config. Must be called before anything is downloaded
+pub fn configure(cache_dir: &Path) -> Result<()>
+{
+	let endpoints = Endpoints::load(cache_dir)?;
+	ENDPOINTS.set(endpoints)
+		.map_err(|_|anyhow::anyhow!("Endpoints were already configured"))
+}
+
+pub(super) fn endpoints() -> &'static Endpoints
+{
+	ENDPOINTS.get_or_init(Endpoints::default)
+}
@@ -1,13 +1,12 @@
-use std::io::Read;
-use anyhow::{Result, Context};
+use std::path::Path;
 
-const EXPORT: &str = "https://content.warframe.com/PublicExport";
-const MANIFEST_TEMPLATE: &str = "https://content.warframe.com/PublicExport/Manifest";
+use anyhow::{Result, Context};
 
 pub fn manifest(name: &str) -> Result<String>
 {
+	super::ensure_online()?;
 	println!("Downloading new manifest: {name}");
-	let url = format!("{MANIFEST_TEMPLATE}/{name}");
+	let url = format!("{}/{name}", super::endpoints().manifest);
 	ureq::get(&url)
 		.call()
 		.context("Sending GET request")?
@@ -16,24 +15,10 @@ pub fn manifest(name: &str) -> Result<String>
 		.context("Parsing manifest as a String")
 }
 
-pub fn index() -> Result<Vec<u8>>
+/// Returns whether the index changed
+pub fn index(path: &Path) -> Result<bool>
 {
-	let index_url = format!("{EXPORT}/index_en.txt.lzma");
-	let response = ureq::get(&index_url)
-		.call()
-		.context("Sending GET request for manifest index")?;
-	let payload_size: usize = response
-		.headers()
-		.get("Content-Length")
-		.context("Could not find Content-Length")
-		.and_then(|cl|cl.to_str().context("Could not read Content-Length"))
-		.and_then(|cl|cl.parse().context("Could not parse Content-Length"))
-		.unwrap_or(0);
-	let mut payload = Vec::with_capacity(payload_size);
-	response
-		.into_body()
-		.into_reader()
-		.read_to_end(&mut payload)
-		.context("Reading response payload")?;
-	Ok(payload)
-}
\ No newline at end of file
+	let index_url = format!("{}/index_en.txt.lzma", super::endpoints().export);
+	super::conditional::fetch(&index_url, path)
+		.context("Downloading manifest index")
+}
@@ -1,8 +1,39 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{bail, Result};
+
+mod conditional;
 mod droptable;
+mod endpoints;
 mod worldstate;
 mod export;
 
+use endpoints::endpoints;
+
 pub use droptable::droptable;
+pub use endpoints::configure;
 pub use export::index;
 pub use export::manifest;
-pub use worldstate::worldstate;
\ No newline at end of file
+pub use worldstate::worldstate;
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Refuse every further network request
+pub fn set_offline()
+{
+	OFFLINE.store(true, Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool
+{
+	OFFLINE.load(Ordering::Relaxed)
+}
+
+fn ensure_online() -> Result<()>
+{
+	if is_offline()
+	{
+		bail!("Network access is disabled in offline mode");
+	}
+	Ok(())
+}
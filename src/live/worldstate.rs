@@ -1,13 +1,10 @@
-use anyhow::{Result, Context};
+use std::path::Path;
 
-const WORLDSTATE: &str = "https://content.warframe.com/dynamic/worldState.php";
+use anyhow::{Result, Context};
 
-pub fn worldstate() -> Result<String>
+/// Returns whether the worldstate changed
+pub fn worldstate(path: &Path) -> Result<bool>
 {
-	ureq::get(WORLDSTATE)
-		.call()
-		.context("Sending GET request for the worldstate")?
-		.into_body()
-		.read_to_string()
-		.context("Parsing response from worldstate GET")
-}
\ No newline at end of file
+	super::conditional::fetch(&super::endpoints().worldstate, path)
+		.context("Downloading the worldstate")
+}
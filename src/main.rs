@@ -3,22 +3,36 @@
 use std::collections::HashMap;
 use std::fs;
 use std::io;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::{bail, Context, Result};
 use eframe::egui;
 
+use crate::build_tree::BuildTree;
+use crate::category::Category;
 use crate::recipe::Recipe;
 use crate::requirement::Requirement;
 use crate::structures::{Data, CommonName, UniqueName, Count};
 
+mod availability;
+mod build_tree;
 mod cache;
+mod category;
+mod drop;
+mod foundry;
+mod invasion;
 mod item_view;
 mod live;
+mod planner;
 mod recipe;
+mod refresh;
 mod relic;
 mod requirement;
+mod shopping_list;
 mod structures;
+mod upgrade;
+mod surplus;
 mod ui;
 
 const ICON_BYTES: &[u8] = include_bytes!("../icon.png");
@@ -40,7 +54,10 @@ pub struct Tracked
 {
 	common_name: CommonName,
 	unique_name: UniqueName,
-	recipes: Vec<(Recipe, Vec<(Requirement, Count)>)>
+	recipes: Vec<(Recipe, Vec<(Requirement, Count)>)>,
+	/// Mods and arcanes are collected rather than crafted
+	goal: Option<Requirement>,
+	build_trees: Vec<BuildTree>
 }
 
 impl Tracked
@@ -52,6 +69,7 @@ impl Tracked
 			.context("searching for resource common name")?;
 
 		let mut recipes = vec![];
+		let mut build_trees = vec![];
 		for recipe_unique_name in db.recipes(unique_name.clone())
 		{
 			let recipe = Recipe::new(db, recipe_unique_name.clone())?;
@@ -63,10 +81,60 @@ impl Tracked
 				components.push((requirement, count));
 			}
 			recipes.push((recipe, components));
+			let build_tree = BuildTree::new(db, recipe_unique_name)
+				.with_context(||format!("Expanding build tree for {unique_name}"))?;
+			build_trees.push(build_tree);
 		}
 
-		anyhow::ensure!(!recipes.is_empty(), format!("Recipe not found for {unique_name}"));
-		Ok(Self{common_name, unique_name, recipes})
+		let goal = match db.category(unique_name.clone())
+		{
+			Some(Category::Mod | Category::Arcane) if recipes.is_empty() =>
+			{
+				build_trees.push(BuildTree::leaf(unique_name.clone(), 1.into(), 1.into()));
+				let requirement = Requirement::new(unique_name.clone(), db)
+					.with_context(||format!("Generating collection data for {unique_name}"))?;
+				Some(requirement)
+			},
+			_ => None
+		};
+
+		anyhow::ensure!(
+			!recipes.is_empty() || goal.is_some(),
+			format!("Recipe not found for {unique_name}"));
+		Ok(Self{common_name, unique_name, recipes, goal, build_trees})
+	}
+
+	/// Total raw resources needed to build this item from scratch
+	pub fn raw_resources(&self) -> HashMap<UniqueName, Count>
+	{
+		let mut raw_resources: HashMap<UniqueName, Count> = HashMap::new();
+		for (unique_name, count) in self.build_trees.iter().flat_map(|t|t.leaves())
+		{
+			*raw_resources.entry(unique_name).or_default() += count;
+		}
+		raw_resources
+	}
+
+	/// Whether the item, or anything used to build it, is `unique_name`
+	pub fn contains(&self, unique_name: &UniqueName) -> bool
+	{
+		self.unique_name == *unique_name
+			|| self.build_trees.iter().any(|t|t.contains(unique_name))
+	}
+
+	/// Total foundry credits to build this item from scratch
+	pub fn credits(&self) -> u64
+	{
+		self.build_trees.iter().map(|t|t.credits()).sum()
+	}
+
+	/// Foundry time to build this item from scratch
+	pub fn build_time(&self) -> Duration
+	{
+		self.build_trees.iter()
+			.map(|t|t.build_time())
+			.max()
+			.unwrap_or_default()
 	}
 }
 
@@ -78,23 +146,33 @@ fn main() -> Result<()>
 		fs::create_dir_all(&cache_dir)?;
 	}
 
-	update_index(&cache_dir)
-		.context("Checking for manifest updates")?;
-
-	let droptable_path = cache_dir.join("droptable.html");
-	let droptable = live::droptable()
-		.context("Downloading scrape droptable")?;
-	fs::write(droptable_path, droptable)?;
+	live::configure(&cache_dir)
+		.context("Loading endpoint config")?;
 
-	let worldstate_path = cache_dir.join("worldstate.json");
-	let worldstate = live::worldstate()
-		.context("Downloading world state")?;
-	fs::write(worldstate_path, worldstate)?;
+	if std::env::args().skip(1).any(|a|a == "--offline")
+	{
+		live::set_offline();
+	}
+	else if !cache_dir.join("index_en.txt.lzma").exists()
+	{
+		// Nothing to show until the first download is done. Later launches
+		// start from the cache and refresh it in the background
+		println!("Nothing cached yet, downloading everything");
+		if let Err(e) = refresh::update_cache(&cache_dir)
+		{
+			eprintln!("Could not update cached data, continuing offline: {e:?}");
+			live::set_offline();
+		}
+	}
 
 	let mut data = Data::from_cache(&cache_dir)?;
+	if let Err(e) = refresh::remove_old_manifests(&cache_dir)
+	{
+		eprintln!("Could not remove stale manifests: {e:?}");
+	}
 
 	let tracked_path = cache_dir.join("tracked.json");
-	let (tracked, owned) = match cache::load_state(&tracked_path, &mut data)
+	let state = match cache::load_state(&tracked_path, &mut data)
 		.context("Loading tracked file")
 	{
 			Ok(to) => to,
@@ -106,6 +184,8 @@ fn main() -> Result<()>
 			Err(e) => bail!(e)
 	};
 
+	let offline = live::is_offline().then(||refresh::cached_file_times(&cache_dir));
+
 	let icon = eframe::icon_data::from_png_bytes(ICON_BYTES)?;
 	let viewport = egui::ViewportBuilder::default()
 		.with_inner_size([1920.0, 1080.0])
@@ -118,40 +198,12 @@ fn main() -> Result<()>
 	eframe::run_native(
 		"Recipe Tracker",
 		native_options,
-		Box::new(|_cc| Ok(Box::new(ui::App::with_state(data, tracked, owned, cache_dir))))).unwrap();
-	Ok(())
-}
-
-fn update_index(dir: &Path) -> Result<()>
-{
-	let index_path = dir.join("index_en.txt.lzma");
-	let index = live::index()
-		.context("Downloading new index")?;
-
-	let parsed_index = cache::parse_index(&mut io::BufReader::new(index.as_slice()))?;
-	remove_old_manifests(dir, &parsed_index)?;
-
-	fs::write(index_path, index)
-		.context("Writing new index to disk")?;
-	Ok(())
-}
-
-fn remove_old_manifests(dir: &Path, index: &HashMap<String, String>) -> Result<()>
-{
-	for file in fs::read_dir(dir)?
-	{
-		let file = file?;
-		let file_name = file.file_name();
-		let file_name = file_name.to_str()
-			.context("Non-utf8 string")?;
-
-		if file_name.starts_with("Export")
-			&& file_name != index[&file_name[0..file_name.len()-26]]
+		Box::new(|cc|
 		{
-			println!("Deleting stale manifest: {file_name}");
-			fs::remove_file(file.path())
-				.with_context(||format!("Deleting file: {file_name}"))?;
-		}
-	}
+			let refresh = (!live::is_offline())
+				.then(||refresh::spawn(cache_dir.clone(), cc.egui_ctx.clone()));
+			let app = ui::App::with_state(data, state, offline, refresh, cache_dir);
+			Ok(Box::new(app))
+		})).unwrap();
 	Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+
+use crate::relic::{Refinement, RelicInventory, RelicTier};
+use crate::structures::{CommonName, Data, UniqueName};
+
+/// An owned relic worth opening
+#[derive(Debug)]
+pub struct Suggestion
+{
+	pub relic: CommonName,
+	pub refinement: Refinement,
+	pub owned: u32,
+	/// Percent chance of getting any needed item from one opening
+	pub chance: f32,
+	/// Needed items the relic contains
+	pub needed: Vec<CommonName>
+}
+
+/// Owned relics containing still needed items, best chance first. Each
+/// reward counts with its chance at the owned refinement, so rare rewards and
+/// unrefined relics weigh less
+pub fn suggestions(
+	db: &Data,
+	inventory: &RelicInventory,
+	needed: &HashSet<UniqueName>) -> Vec<Suggestion>
+{
+	let mut suggestions = vec![];
+	for (relic, refinements) in inventory
+	{
+		let contents: Vec<_> = db.relic_contents(relic.clone())
+			.into_iter()
+			.filter(|(reward, _, _)|needed.contains(reward))
+			.collect();
+		if contents.is_empty()
+		{
+			continue
+		}
+		let names: Vec<_> = contents.iter()
+			.map(|(reward, _, _)|db.item_common_name(reward.clone())
+				.unwrap_or_else(||reward.clone().into()))
+			.collect();
+
+		for (&refinement, &owned) in refinements.iter().filter(|&(_, &o)|o > 0)
+		{
+			let chance = contents.iter()
+				.map(|(_, rarity, chances)|chances.iter()
+					.find(|(r, _)|*r == refinement)
+					.map(|&(_, c)|c)
+					.unwrap_or_else(||rarity.chance(refinement)))
+				.sum();
+			suggestions.push(Suggestion
+			{
+				relic: relic.clone(),
+				refinement,
+				owned,
+				chance,
+				needed: names.clone()
+			});
+		}
+	}
+	suggestions.sort_by(|a, b|b.chance.total_cmp(&a.chance)
+		.then_with(||a.relic.cmp(&b.relic))
+		.then_with(||a.refinement.cmp(&b.refinement)));
+	suggestions
+}
+
+/// Relic tiers worth running fissures for, from farmable or owned relics that
+/// contain a still needed item
+pub fn useful_tiers(
+	db: &Data,
+	inventory: &RelicInventory,
+	needed: &HashSet<UniqueName>) -> HashSet<RelicTier>
+{
+	let farmable = needed.iter()
+		.flat_map(|item|
+		{
+			let active = db.active_relics(item.clone()).unwrap_or_default();
+			let resurgence = db.resurgence_relics(item.clone()).unwrap_or_default();
+			active.into_iter().chain(resurgence)
+		})
+		.flat_map(|relic|RelicTier::of_relic(relic.name()));
+	let owned = suggestions(db, inventory, needed)
+		.into_iter()
+		.flat_map(|s|RelicTier::of_relic(s.relic.as_str()));
+	farmable.chain(owned).collect()
+}
@@ -1,7 +1,12 @@
+use std::time::Duration;
+
 use anyhow::{Result, Context};
 
+use crate::category::Category;
+use crate::drop::DropSource;
+use crate::invasion::InvasionReward;
 use crate::item_view::ItemView;
-use crate::structures::{CommonName, UniqueName, Data};
+use crate::structures::{BuildInfo, CommonName, UniqueName, Data};
 use crate::relic::Relic;
 
 #[derive(Debug)]
@@ -9,6 +14,7 @@ pub struct Recipe
 {
 	common_name: CommonName,
 	unique_name: UniqueName,
+	build_info: BuildInfo,
 	recipe_type: RecipeType
 }
 
@@ -34,7 +40,11 @@ impl Recipe
 
 	pub fn with_common_name(db: &Data, unique_name: UniqueName, common_name: CommonName) -> Result<Self>
 	{
-		let recipe_type = if common_name.as_str().contains("Prime")
+		let build_info = db.recipe_build_info(unique_name.clone())
+			.unwrap_or_default();
+		let from_relics = db.category(unique_name.clone()) == Some(Category::Blueprint)
+			&& db.is_prime_part(unique_name.clone());
+		let recipe_type = if from_relics
 		{
 			let active_relics = db.active_relics(unique_name.clone())
 				.unwrap_or_default();
@@ -49,14 +59,39 @@ impl Recipe
 		}
 		else
 		{
-			let available_from_invasion = db.available_from_invasion(unique_name.clone());
+			let invasions = db.invasions(unique_name.clone());
+			let drop_sources = db.drop_sources(common_name.clone());
 			let recipe = NormalRecipe
 			{
-				available_from_invasion
+				invasions,
+				drop_sources
 			};
 			RecipeType::Normal(recipe)
 		};
-		Ok(Recipe{common_name, unique_name, recipe_type})
+		Ok(Recipe{common_name, unique_name, build_info, recipe_type})
+	}
+
+	/// Credits charged by the foundry for a single craft
+	pub fn credits(&self) -> u32
+	{
+		self.build_info.credits
+	}
+
+	pub fn build_time(&self) -> Duration
+	{
+		self.build_info.build_time
+	}
+
+	/// Platinum needed to skip the build time
+	pub fn rush_cost(&self) -> u32
+	{
+		self.build_info.rush_cost
+	}
+
+	/// Units produced by a single craft
+	pub fn output_quantity(&self) -> u32
+	{
+		self.build_info.quantity
 	}
 }
 
@@ -96,17 +131,34 @@ impl ItemView for Recipe
 		}
 	}
 
-	fn available_from_invasion(&self) -> bool
+	fn invasions(&self) -> &[InvasionReward]
+	{
+		if let RecipeType::Normal(nr) = &self.recipe_type
+		{
+			&nr.invasions
+		}
+		else
+		{
+			&[]
+		}
+	}
+
+	fn drop_sources(&self) -> &[DropSource]
 	{
-		if let RecipeType::Normal(pr) = &self.recipe_type
+		if let RecipeType::Normal(nr) = &self.recipe_type
 		{
-			pr.available_from_invasion
+			&nr.drop_sources
 		}
 		else
 		{
-			false
+			&[]
 		}
 	}
+
+	fn recipe_unique_name(&self) -> Option<UniqueName>
+	{
+		Some(self.unique_name.clone())
+	}
 }
 
 impl ItemView for &Recipe
@@ -131,9 +183,19 @@ impl ItemView for &Recipe
 		(*self).active_relics()
 	}
 
-	fn available_from_invasion(&self) -> bool
+	fn invasions(&self) -> &[InvasionReward]
+	{
+		(*self).invasions()
+	}
+
+	fn drop_sources(&self) -> &[DropSource]
+	{
+		(*self).drop_sources()
+	}
+
+	fn recipe_unique_name(&self) -> Option<UniqueName>
 	{
-		(*self).available_from_invasion()
+		(*self).recipe_unique_name()
 	}
 }
 
@@ -147,5 +209,6 @@ pub struct PrimeRecipe
 #[derive(Debug)]
 pub struct NormalRecipe
 {
-	pub available_from_invasion: bool
+	pub invasions: Vec<InvasionReward>,
+	pub drop_sources: Vec<DropSource>
 }
\ No newline at end of file
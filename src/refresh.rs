@@ -0,0 +1,161 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use eframe::egui;
+
+use crate::cache;
+use crate::live;
+use crate::structures::Data;
+
+/// Downloaded files whose age is shown when running offline
+const CACHED_FILES: [&str; 3] = ["index_en.txt.lzma", "droptable.html", "worldstate.json"];
+const WORLDSTATE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Sent from the refresh worker to the UI
+pub enum Message
+{
+	Status(String),
+	/// Manifests or droptable changed, so everything was rebuilt
+	Data(Box<Data>),
+	/// Only the worldstate changed and needs reloading
+	Worldstate,
+	Failed(String)
+}
+
+/// Download everything up front. Only needed when nothing has been cached yet
+pub fn update_cache(dir: &Path) -> Result<()>
+{
+	update_index(dir)
+		.context("Checking for manifest updates")?;
+	live::droptable(&dir.join("droptable.html"))
+		.context("Updating scrape droptable")?;
+	live::worldstate(&dir.join("worldstate.json"))
+		.context("Updating world state")?;
+	Ok(())
+}
+
+/// Returns whether the index changed
+fn update_index(dir: &Path) -> Result<bool>
+{
+	let index_path = dir.join("index_en.txt.lzma");
+	live::index(&index_path)
+		.context("Downloading new index")
+}
+
+/// Delete manifests the current index no longer refers to. Only call this once
+/// `Data` has been built from the current index, as until then the old
+/// manifests are all an offline start has to go on
+pub fn remove_old_manifests(dir: &Path) -> Result<()>
+{
+	let index = cache::load_index(&dir.join("index_en.txt.lzma"))?;
+	for file in fs::read_dir(dir)?
+	{
+		let file = file?;
+		let file_name = file.file_name();
+		let file_name = file_name.to_str()
+			.context("Non-utf8 string")?;
+
+		// Index entries are the file name followed by a 26 character hash
+		let current = file_name.len()
+			.checked_sub(26)
+			.and_then(|n|file_name.get(..n))
+			.and_then(|m|index.get(m));
+		if file_name.starts_with("Export")
+			&& current.is_none_or(|current|current != file_name)
+		{
+			println!("Deleting stale manifest: {file_name}");
+			fs::remove_file(file.path())
+				.with_context(||format!("Deleting file: {file_name}"))?;
+		}
+	}
+	Ok(())
+}
+
+/// Modification time of each cached download
+pub fn cached_file_times(dir: &Path) -> Vec<(&'static str, SystemTime)>
+{
+	CACHED_FILES
+		.iter()
+		.flat_map(|&f|
+		{
+			let modified = fs::metadata(dir.join(f))
+				.and_then(|m|m.modified())
+				.ok()?;
+			Some((f, modified))
+		})
+		.collect()
+}
+
+/// Refresh the cache in the background, then keep polling the worldstate.
+/// The UI is woken up whenever a message is sent
+pub fn spawn(dir: PathBuf, ctx: egui::Context) -> Receiver<Message>
+{
+	let (sender, receiver) = mpsc::channel();
+	thread::spawn(move ||
+	{
+		let send = |message|
+		{
+			let sent = sender.send(message).is_ok();
+			ctx.request_repaint();
+			sent
+		};
+
+		if let Err(e) = refresh(&dir, &send)
+		{
+			send(Message::Failed(format!("{e:#}")));
+		}
+		loop
+		{
+			thread::sleep(WORLDSTATE_INTERVAL);
+			let sent = match live::worldstate(&dir.join("worldstate.json"))
+			{
+				Ok(true) => send(Message::Worldstate),
+				Ok(false) => true,
+				Err(e) => send(Message::Failed(format!("{e:#}")))
+			};
+			// The UI has gone away
+			if !sent
+			{
+				break
+			}
+		}
+	});
+	receiver
+}
+
+fn refresh(dir: &Path, send: &impl Fn(Message) -> bool) -> Result<()>
+{
+	send(Message::Status("Checking for manifest updates".to_owned()));
+	let index_changed = update_index(dir)
+		.context("Checking for manifest updates")?;
+
+	send(Message::Status("Checking for droptable updates".to_owned()));
+	let droptable_changed = live::droptable(&dir.join("droptable.html"))
+		.context("Updating scrape droptable")?;
+
+	send(Message::Status("Checking for world state updates".to_owned()));
+	let worldstate_changed = live::worldstate(&dir.join("worldstate.json"))
+		.context("Updating world state")?;
+
+	if index_changed || droptable_changed
+	{
+		send(Message::Status("Rebuilding data".to_owned()));
+		let data = Data::from_cache(dir)?;
+		send(Message::Data(Box::new(data)));
+		if index_changed
+		{
+			remove_old_manifests(dir)
+				.context("Removing stale manifests")?;
+		}
+	}
+	else if worldstate_changed
+	{
+		send(Message::Worldstate);
+	}
+	send(Message::Status(String::new()));
+	Ok(())
+}
@@ -1,9 +1,13 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
 use anyhow::{anyhow, Result, Error};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::structures::CommonName;
+use crate::drop::DropSource;
+use crate::structures::{CommonName, ResurgenceOffer};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all="UPPERCASE")]
 pub enum Rarity
 {
@@ -26,22 +30,254 @@ impl TryFrom<&str> for Rarity
 	}
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+impl Rarity
+{
+	/// Standard percent chance of a single reward of this rarity, used when the
+	/// droptable doesn't list the relic
+	pub fn chance(self, refinement: Refinement) -> f32
+	{
+		match (self, refinement)
+		{
+			(Self::Common, Refinement::Intact)=>25.33,
+			(Self::Common, Refinement::Exceptional)=>23.33,
+			(Self::Common, Refinement::Flawless)=>20.0,
+			(Self::Common, Refinement::Radiant)=>16.67,
+			(Self::Uncommon, Refinement::Intact)=>11.0,
+			(Self::Uncommon, Refinement::Exceptional)=>13.0,
+			(Self::Uncommon, Refinement::Flawless)=>17.0,
+			(Self::Uncommon, Refinement::Radiant)=>20.0,
+			(Self::Rare, Refinement::Intact)=>2.0,
+			(Self::Rare, Refinement::Exceptional)=>4.0,
+			(Self::Rare, Refinement::Flawless)=>6.0,
+			(Self::Rare, Refinement::Radiant)=>10.0
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Refinement
+{
+	Intact,
+	Exceptional,
+	Flawless,
+	Radiant
+}
+
+impl Refinement
+{
+	pub const ALL: [Self; 4] = [Self::Intact, Self::Exceptional, Self::Flawless, Self::Radiant];
+}
+
+impl Display for Refinement
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			Self::Intact=>"Intact".fmt(f),
+			Self::Exceptional=>"Exceptional".fmt(f),
+			Self::Flawless=>"Flawless".fmt(f),
+			Self::Radiant=>"Radiant".fmt(f)
+		}
+	}
+}
+
+impl TryFrom<&str> for Refinement
+{
+	type Error = Error;
+	fn try_from(i: &str) -> Result<Self, Self::Error>
+	{
+		match i
+		{
+			"Intact"=>Ok(Self::Intact),
+			"Exceptional"=>Ok(Self::Exceptional),
+			"Flawless"=>Ok(Self::Flawless),
+			"Radiant"=>Ok(Self::Radiant),
+			_=>Err(anyhow!("Unknown refinement: {}", i))
+		}
+	}
+}
+
+/// Relic era, which decides the void fissures it can be opened in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum RelicTier
+{
+	Lith,
+	Meso,
+	Neo,
+	Axi,
+	Requiem,
+	/// Fissures that accept any relic
+	Omnia
+}
+
+impl RelicTier
+{
+	/// Tier of a relic from its name, e.g. `Lith A1 Relic`
+	pub fn of_relic(name: &str) -> Option<Self>
+	{
+		name.split(' ')
+			.next()
+			.and_then(|t|t.try_into().ok())
+	}
+
+	/// Whether a relic of this tier can be opened in a fissure of `fissure`
+	pub fn opens_in(self, fissure: Self) -> bool
+	{
+		self == fissure || fissure == Self::Omnia
+	}
+}
+
+impl Display for RelicTier
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			Self::Lith=>"Lith".fmt(f),
+			Self::Meso=>"Meso".fmt(f),
+			Self::Neo=>"Neo".fmt(f),
+			Self::Axi=>"Axi".fmt(f),
+			Self::Requiem=>"Requiem".fmt(f),
+			Self::Omnia=>"Omnia".fmt(f)
+		}
+	}
+}
+
+impl TryFrom<&str> for RelicTier
+{
+	type Error = Error;
+	fn try_from(i: &str) -> Result<Self, Self::Error>
+	{
+		match i
+		{
+			"Lith"=>Ok(Self::Lith),
+			"Meso"=>Ok(Self::Meso),
+			"Neo"=>Ok(Self::Neo),
+			"Axi"=>Ok(Self::Axi),
+			"Requiem"=>Ok(Self::Requiem),
+			"Omnia"=>Ok(Self::Omnia),
+			_=>Err(anyhow!("Unknown relic tier: {}", i))
+		}
+	}
+}
+
+/// Owned relics by name and refinement
+pub type RelicInventory = HashMap<CommonName, HashMap<Refinement, u32>>;
+
+#[derive(Debug)]
 pub struct Relic
 {
 	name: CommonName,
-	pub rarity: Rarity
+	pub rarity: Rarity,
+	/// Where the relic drops, best chance first
+	drop_sources: Vec<DropSource>,
+	/// Percent chance of the tracked item at each refinement
+	chances: Vec<(Refinement, f32)>,
+	/// Price and end of sale, if Varzia is selling the relic
+	resurgence: Option<ResurgenceOffer>
 }
 
 impl Relic
 {
-	pub fn new(name: CommonName, rarity: Rarity) -> Self
+	pub fn new(
+		name: CommonName,
+		rarity: Rarity,
+		drop_sources: Vec<DropSource>,
+		chances: Vec<(Refinement, f32)>,
+		resurgence: Option<ResurgenceOffer>) -> Self
+	{
+		Self{name, rarity, drop_sources, chances, resurgence}
+	}
+
+	pub fn resurgence(&self) -> Option<ResurgenceOffer>
 	{
-		Self{name, rarity}
+		self.resurgence
 	}
 
 	pub fn name(&self) -> &str
 	{
 		self.name.as_str()
 	}
-}
\ No newline at end of file
+
+	pub fn drop_sources(&self) -> &[DropSource]
+	{
+		&self.drop_sources
+	}
+
+	/// Percent chance of the tracked item when opening this relic
+	pub fn chance(&self, refinement: Refinement) -> Option<f32>
+	{
+		self.chances
+			.iter()
+			.find(|(r, _)|*r == refinement)
+			.map(|&(_, chance)|chance)
+	}
+
+	/// Expected number of runs to get `count` of the tracked item, with every
+	/// member of a squad of `squad_size` opening this relic at `refinement`
+	pub fn expected_runs(&self, refinement: Refinement, squad_size: u32, count: u32) -> Option<f32>
+	{
+		let chance = self.chance(refinement)? / 100.0;
+		let per_run = 1.0 - (1.0 - chance).powi(squad_size as i32);
+		(per_run > 0.0).then(||count as f32 / per_run)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn relic(chances: Vec<(Refinement, f32)>) -> Relic
+	{
+		Relic::new("Lith C7 Relic".into(), Rarity::Rare, vec![], chances, None)
+	}
+
+	fn assert_close(actual: Option<f32>, expected: f32)
+	{
+		let actual = actual.expect("expected a number of runs");
+		assert!((actual - expected).abs() < 0.01, "expected {expected}, got {actual}");
+	}
+
+	#[test]
+	fn solo_runs_are_the_inverse_of_the_chance()
+	{
+		let relic = relic(vec![(Refinement::Intact, 2.0), (Refinement::Radiant, 10.0)]);
+		assert_close(relic.expected_runs(Refinement::Intact, 1, 1), 50.0);
+		assert_close(relic.expected_runs(Refinement::Radiant, 1, 1), 10.0);
+	}
+
+	#[test]
+	fn radshare_runs_use_the_chance_of_any_squad_member_getting_it()
+	{
+		let relic = relic(vec![(Refinement::Intact, 2.0), (Refinement::Radiant, 10.0)]);
+		// 1 - 0.98^4 = 0.07763
+		assert_close(relic.expected_runs(Refinement::Intact, 4, 1), 12.881);
+		// 1 - 0.9^4 = 0.3439
+		assert_close(relic.expected_runs(Refinement::Radiant, 4, 1), 2.908);
+	}
+
+	#[test]
+	fn runs_scale_with_count()
+	{
+		let relic = relic(vec![(Refinement::Intact, 2.0)]);
+		assert_close(relic.expected_runs(Refinement::Intact, 1, 3), 150.0);
+		assert_close(relic.expected_runs(Refinement::Intact, 4, 2), 25.763);
+	}
+
+	#[test]
+	fn no_runs_without_a_chance()
+	{
+		let relic = relic(vec![(Refinement::Intact, 0.0)]);
+		assert_eq!(relic.expected_runs(Refinement::Intact, 4, 1), None);
+		assert_eq!(relic.expected_runs(Refinement::Radiant, 1, 1), None);
+	}
+
+	#[test]
+	fn standard_chances_match_the_rarity()
+	{
+		assert_eq!(Rarity::Rare.chance(Refinement::Intact), 2.0);
+		assert_eq!(Rarity::Common.chance(Refinement::Radiant), 16.67);
+	}
+}
@@ -1,5 +1,8 @@
 use anyhow::{Result, Context};
 
+use crate::category::Category;
+use crate::drop::DropSource;
+use crate::invasion::InvasionReward;
 use crate::item_view::ItemView;
 use crate::structures::{CommonName, UniqueName, Data};
 use crate::relic::Relic;
@@ -87,21 +90,48 @@ impl ItemView for Requirement
 		}
 	}
 
-	fn available_from_invasion(&self) -> bool
+	fn invasions(&self) -> &[InvasionReward]
 	{
 		match &self.requirement_type
 		{
 			RequirementType::Component(Component::Normal(nc))=>
 			{
-				nc.available_from_invasion
+				&nc.invasions
 			},
 
 			RequirementType::CraftComponent(cc)=>
 			{
-				cc.available_from_invasion()
+				cc.invasions()
 			},
 
-			_ => false
+			_ => &[]
+		}
+	}
+
+	fn drop_sources(&self) -> &[DropSource]
+	{
+		match &self.requirement_type
+		{
+			RequirementType::Component(Component::Normal(nc))=>
+			{
+				&nc.drop_sources
+			},
+
+			RequirementType::CraftComponent(cc)=>
+			{
+				cc.drop_sources()
+			},
+
+			_ => &[]
+		}
+	}
+
+	fn recipe_unique_name(&self) -> Option<UniqueName>
+	{
+		match &self.requirement_type
+		{
+			RequirementType::CraftComponent(recipe) => recipe.recipe_unique_name(),
+			RequirementType::Component(_) => None
 		}
 	}
 }
@@ -128,9 +158,19 @@ impl ItemView for &Requirement
 		(*self).active_relics()
 	}
 
-	fn available_from_invasion(&self) -> bool
+	fn invasions(&self) -> &[InvasionReward]
+	{
+		(*self).invasions()
+	}
+
+	fn drop_sources(&self) -> &[DropSource]
+	{
+		(*self).drop_sources()
+	}
+
+	fn recipe_unique_name(&self) -> Option<UniqueName>
 	{
-		(*self).available_from_invasion()
+		(*self).recipe_unique_name()
 	}
 }
 
@@ -152,7 +192,9 @@ impl Component
 {
 	pub fn new(unique_name: UniqueName, common_name: CommonName, db: &Data) -> Self
 	{
-		if common_name.as_str().contains("Prime")
+		let from_relics = db.category(unique_name.clone()) == Some(Category::Component)
+			&& db.is_prime_part(unique_name.clone());
+		if from_relics
 		{
 			let active_relics = db.active_relics(unique_name.clone())
 				.unwrap_or_default();
@@ -167,10 +209,12 @@ impl Component
 		}
 		else
 		{
-			let available_from_invasion = db.available_from_invasion(unique_name);
+			let invasions = db.invasions(unique_name);
+			let drop_sources = db.drop_sources(common_name);
 			let normal_component = NormalComponent
 			{
-				available_from_invasion
+				invasions,
+				drop_sources
 			};
 			Self::Normal(normal_component)
 		}
@@ -180,7 +224,8 @@ impl Component
 #[derive(Debug)]
 struct NormalComponent
 {
-	available_from_invasion: bool
+	invasions: Vec<InvasionReward>,
+	drop_sources: Vec<DropSource>
 }
 
 #[derive(Debug)]
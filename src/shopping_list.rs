@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use crate::structures::{CommonName, Count, Data, UniqueName};
+use crate::Tracked;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Entry
+{
+	pub common_name: CommonName,
+	pub unique_name: UniqueName,
+	/// Total needed across every tracked item, ignoring what is owned
+	pub required: Count,
+	/// What is still needed after using owned items and components
+	pub missing: Count
+}
+
+/// Combined raw resources still missing across every tracked item
+pub fn shopping_list(
+	db: &Data,
+	tracked: &[Tracked],
+	owned: &HashMap<UniqueName, u32>) -> Vec<Entry>
+{
+	let mut required: HashMap<UniqueName, Count> = HashMap::new();
+	let mut missing = HashMap::new();
+	let mut available = owned.clone();
+	for tracked in tracked
+	{
+		for (unique_name, count) in tracked.raw_resources()
+		{
+			*required.entry(unique_name).or_default() += count;
+		}
+		for build_tree in &tracked.build_trees
+		{
+			build_tree.missing(&mut available, &mut missing);
+		}
+	}
+
+	let mut entries: Vec<_> = missing.into_iter()
+		.map(|(unique_name, missing)|
+		{
+			let common_name = db.resource_common_name(unique_name.clone())
+				.unwrap_or_else(||unique_name.clone().into());
+			let required = required.get(&unique_name)
+				.copied()
+				.unwrap_or(missing);
+			Entry{common_name, unique_name, required, missing}
+		})
+		.collect();
+	entries.sort();
+	entries
+}
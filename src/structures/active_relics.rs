@@ -1,8 +1,10 @@
 use std::collections::HashSet;
 
+use serde::{Deserialize, Serialize};
+
 use super::types::CommonName;
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct ActiveRelics
 {
 	common_names: HashSet<CommonName>
@@ -19,4 +21,13 @@ impl ActiveRelics
 	{
 		self.common_names.insert(common_name);
 	}
-}
\ No newline at end of file
+}
+
+/// Whether a droptable item is a relic
+pub fn is_relic(item: &str) -> bool
+{
+	item.starts_with("Lith")
+	||item.starts_with("Meso")
+	||item.starts_with("Neo")
+	||item.starts_with("Axi")
+}
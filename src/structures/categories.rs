@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::category::Category;
+use super::types::UniqueName;
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct Categories
+{
+	by_unique_name: HashMap<UniqueName, Category>
+}
+
+impl Categories
+{
+	pub fn fetch_by_unique_name(&self, unique_name: UniqueName) -> Option<Category>
+	{
+		self.by_unique_name.get(&unique_name).copied()
+	}
+
+	pub fn add(&mut self, unique_name: UniqueName, category: Category)
+	{
+		self.by_unique_name.insert(unique_name, category);
+	}
+}
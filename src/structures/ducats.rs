@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::types::UniqueName;
+
+/// What Baro Ki'Teer's kiosks pay for prime parts
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct Ducats
+{
+	by_unique_name: HashMap<UniqueName, u32>
+}
+
+impl Ducats
+{
+	pub fn fetch_by_unique_name(&self, unique_name: UniqueName) -> Option<u32>
+	{
+		self.by_unique_name.get(&unique_name).copied()
+	}
+
+	pub fn add(&mut self, unique_name: UniqueName, ducats: u32)
+	{
+		self.by_unique_name.insert(unique_name, ducats);
+	}
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::drop::{DropRarity, EnemyDropKind};
+use super::index::{item_key, Index};
+use super::types::CommonName;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EnemyDrop
+{
+	pub kind: EnemyDropKind,
+	pub enemy: CommonName,
+	/// Percent chance of the enemy dropping anything from its table
+	pub enemy_chance: f32,
+	pub item: CommonName,
+	pub rarity: DropRarity,
+	/// Percent chance of this item, once the enemy drops something
+	pub chance: f32
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct EnemyDrops
+{
+	rows: Vec<EnemyDrop>,
+	by_item: Index<String>
+}
+
+impl EnemyDrops
+{
+	pub fn fetch_by_item(&self, item: CommonName) -> impl Iterator<Item = &EnemyDrop> + '_
+	{
+		self.by_item
+			.get(&item_key(item.as_str()))
+			.map(|i|&self.rows[i])
+	}
+
+	pub fn add(&mut self, drop: EnemyDrop)
+	{
+		self.by_item.add(item_key(drop.item.as_str()), self.rows.len());
+		self.rows.push(drop);
+	}
+}
@@ -0,0 +1,39 @@
+use std::time::SystemTime;
+
+use crate::relic::RelicTier;
+use super::types::CommonName;
+
+#[derive(Clone, Debug)]
+pub struct Fissure
+{
+	/// Node name with its system, e.g. `Apollodorus (Mercury)`
+	pub node: CommonName,
+	pub mission_type: CommonName,
+	pub tier: RelicTier,
+	pub expiry: SystemTime,
+	pub steel_path: bool
+}
+
+#[derive(Default, Debug)]
+pub struct Fissures
+{
+	rows: Vec<Fissure>
+}
+
+impl Fissures
+{
+	/// Fissures that haven't expired yet, soonest to expire first
+	pub fn active(&self) -> impl Iterator<Item = &Fissure> + '_
+	{
+		let now = SystemTime::now();
+		self.rows
+			.iter()
+			.filter(move |f|f.expiry > now)
+	}
+
+	pub fn add(&mut self, fissure: Fissure)
+	{
+		let i = self.rows.partition_point(|f|f.expiry <= fissure.expiry);
+		self.rows.insert(i, fissure);
+	}
+}
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+/// Maps a lookup key to the rows it appears in, in insertion order
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "K: Deserialize<'de> + Hash + Eq"))]
+pub struct Index<K>
+{
+	rows: HashMap<K, Vec<usize>>
+}
+
+impl<K> Default for Index<K>
+{
+	fn default() -> Self
+	{
+		Self{rows: HashMap::new()}
+	}
+}
+
+impl<K> Index<K>
+	where
+		K: Hash + Eq
+{
+	pub fn add(&mut self, key: K, row: usize)
+	{
+		self.rows.entry(key).or_default().push(row);
+	}
+
+	pub fn get<'a>(&'a self, key: &K) -> impl Iterator<Item = usize> + use<'a, K>
+	{
+		self.rows.get(key)
+			.into_iter()
+			.flatten()
+			.copied()
+	}
+}
+
+/// Key for case insensitive lookups
+pub fn fold(key: &str) -> String
+{
+	key.to_ascii_lowercase()
+}
+
+/// Key for droptable items, ignoring any stack size such as `3X Nitain Extract`
+/// and pre-refined relics
+pub fn item_key(item: &str) -> String
+{
+	let item = item.trim_end_matches(" (Radiant)");
+	let item = item.split_once("X ")
+		.filter(|(count, _)|!count.is_empty() && count.chars().all(|c|c.is_ascii_digit()))
+		.map(|(_, item)|item)
+		.unwrap_or(item);
+	fold(item)
+}
@@ -1,22 +1,51 @@
 use std::collections::HashSet;
 
-use super::types::UniqueName;
+use crate::invasion::Faction;
+use super::index::Index;
+use super::types::{CommonName, UniqueName};
+
+#[derive(Clone, Debug)]
+pub struct Invasion
+{
+	/// Node name with its system, e.g. `Apollodorus (Mercury)`
+	pub node: CommonName,
+	pub attacker: Faction,
+	pub defender: Faction,
+	/// Rewards for supporting the attacker, with their counts
+	pub attacker_rewards: Vec<(UniqueName, u32)>,
+	pub defender_rewards: Vec<(UniqueName, u32)>,
+	/// Percent of the way to the attacker winning
+	pub attacker_progress: f32
+}
 
 #[derive(Default, Debug)]
 pub struct Invasions
 {
-	unique_name: HashSet<UniqueName>
+	rows: Vec<Invasion>,
+	by_reward: Index<UniqueName>
 }
 
 impl Invasions
 {
-	pub fn drops_from_invasion(&self, unique_name: UniqueName) -> bool
+	pub fn fetch_by_reward(&self, unique_name: UniqueName) -> impl Iterator<Item = &Invasion> + '_
 	{
-		self.unique_name.contains(&unique_name)
+		self.by_reward
+			.get(&unique_name)
+			.map(|i|&self.rows[i])
 	}
 
-	pub fn add(&mut self, common_name: UniqueName)
+	pub fn add(&mut self, invasion: Invasion)
 	{
-		self.unique_name.insert(common_name);
+		let row = self.rows.len();
+		let rewards: HashSet<_> = invasion.attacker_rewards
+			.iter()
+			.chain(&invasion.defender_rewards)
+			.map(|(reward, _)|reward)
+			.collect();
+		for reward in rewards
+		{
+			self.by_reward.add(reward.clone(), row);
+		}
+		self.rows.push(invasion);
 	}
-}
\ No newline at end of file
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::drop::{DropRarity, RewardSource, Rotation};
+use super::index::{item_key, Index};
+use super::types::CommonName;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LocationReward
+{
+	pub source: RewardSource,
+	pub location: CommonName,
+	pub rotation: Option<Rotation>,
+	pub stage: Option<CommonName>,
+	pub item: CommonName,
+	pub rarity: DropRarity,
+	/// Percent
+	pub chance: f32
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct LocationRewards
+{
+	rows: Vec<LocationReward>,
+	by_item: Index<String>
+}
+
+impl LocationRewards
+{
+	pub fn fetch_by_item(&self, item: CommonName) -> impl Iterator<Item = &LocationReward> + '_
+	{
+		self.by_item
+			.get(&item_key(item.as_str()))
+			.map(|i|&self.rows[i])
+	}
+
+	pub fn add(&mut self, reward: LocationReward)
+	{
+		self.by_item.add(item_key(reward.item.as_str()), self.rows.len());
+		self.rows.push(reward);
+	}
+}
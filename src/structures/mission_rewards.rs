@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::drop::{DropRarity, Rotation};
+use super::index::{item_key, Index};
+use super::types::CommonName;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MissionReward
+{
+	pub planet: CommonName,
+	pub node: CommonName,
+	pub mission_type: CommonName,
+	pub rotation: Option<Rotation>,
+	pub item: CommonName,
+	pub rarity: DropRarity,
+	/// Percent
+	pub chance: f32
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct MissionRewards
+{
+	rows: Vec<MissionReward>,
+	by_item: Index<String>
+}
+
+impl MissionRewards
+{
+	pub fn fetch_by_item(&self, item: CommonName) -> impl Iterator<Item = &MissionReward> + '_
+	{
+		self.by_item
+			.get(&item_key(item.as_str()))
+			.map(|i|&self.rows[i])
+	}
+
+	pub fn add(&mut self, reward: MissionReward)
+	{
+		self.by_item.add(item_key(reward.item.as_str()), self.rows.len());
+		self.rows.push(reward);
+	}
+}
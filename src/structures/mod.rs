@@ -1,9 +1,12 @@
 use std::collections::HashMap;
 use std::hash::Hash;
 use std::path::Path;
+use std::time::{Duration, SystemTime};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 
+mod index;
 mod invasions;
 mod types;
 mod recipes;
@@ -12,32 +15,141 @@ mod relics;
 mod relic_rewards;
 mod resources;
 mod active_relics;
-mod resurgence_relics;
+mod resurgence;
+mod mission_rewards;
+mod relic_chances;
+mod location_rewards;
+mod enemy_drops;
+mod categories;
+mod ducats;
+mod fissures;
+mod regions;
+mod snapshot;
+mod upgrades;
+mod void_traders;
 
 use crate::cache;
-use crate::relic::Relic;
+use crate::live;
+use crate::category::Category;
+use crate::drop::DropSource;
+use crate::invasion::InvasionReward;
+use crate::relic::{Rarity, Refinement, Relic};
 use active_relics::ActiveRelics;
-use invasions::Invasions;
+use categories::Categories;
+use ducats::Ducats;
+use enemy_drops::{EnemyDrop, EnemyDrops};
+use location_rewards::{LocationReward, LocationRewards};
+use mission_rewards::{MissionReward, MissionRewards};
+use relic_chances::{RelicChance, RelicChances};
+use fissures::Fissures;
+use invasions::{Invasion, Invasions};
 use recipes::Recipes;
+use regions::Regions;
 use relics::Relics;
 use requires::Requires;
 use relic_rewards::RelicRewards;
 use resources::Resources;
-use resurgence_relics::ResurgenceRelics;
+use resurgence::Resurgence;
+use upgrades::Upgrades;
+use void_traders::VoidTraders;
+pub use fissures::Fissure;
+pub use recipes::BuildInfo;
+pub use resurgence::ResurgenceOffer;
+pub use upgrades::Upgrade;
+pub use void_traders::{VoidTrader, VoidTraderItem};
 pub use types::{UniqueName, Count, CommonName};
 
+/// (Reward, Rarity, Chance at each refinement)
+pub type RelicContent = (UniqueName, Rarity, Vec<(Refinement, f32)>);
 
-#[derive(Debug)]
+/// Manifests of other items built in the foundry, e.g. `Specters`, keys,
+/// extractors and glyphs
+const FOUNDRY_ITEM_MANIFESTS: [(&str, Category); 4] = [
+	("ExportGear_en.json", Category::Gear),
+	("ExportKeys_en.json", Category::Key),
+	("ExportDrones_en.json", Category::Extractor),
+	("ExportCustoms_en.json", Category::Cosmetic)];
+
+/// Manifests with their own loader in `from_manifests`, which also skips the
+/// lists in them that aren't items, such as abilities, mod sets and relics
+const DEDICATED_MANIFESTS: [&str; 8] = [
+	"ExportResources_en.json",
+	"ExportWarframes_en.json",
+	"ExportWeapons_en.json",
+	"ExportSentinels_en.json",
+	"ExportUpgrades_en.json",
+	"ExportRecipes_en.json",
+	"ExportRelicArcane_en.json",
+	"ExportRegions_en.json"];
+
+/// Worldstate tables are skipped by the snapshot, as they change on every
+/// download and are cheap to parse
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct Data
 {
 	active_relics: ActiveRelics,
+	#[serde(skip)]
 	invasions: Invasions,
 	recipes: Recipes,
 	relics: Relics,
 	relic_rewards: RelicRewards,
 	requires: Requires,
 	resources: Resources,
-	resurgence_relics: ResurgenceRelics,
+	#[serde(skip)]
+	resurgence: Resurgence,
+	mission_rewards: MissionRewards,
+	relic_chances: RelicChances,
+	location_rewards: LocationRewards,
+	enemy_drops: EnemyDrops,
+	regions: Regions,
+	ducats: Ducats,
+	categories: Categories,
+	upgrades: Upgrades,
+	#[serde(skip)]
+	fissures: Fissures,
+	#[serde(skip)]
+	void_traders: VoidTraders,
+}
+
+fn is_cached(cache_dir: &Path, index: &HashMap<String, String>, manifest: &str) -> bool
+{
+	index.get(manifest)
+		.is_some_and(|file_name|cache_dir.join(file_name).exists())
+}
+
+/// Load a manifest that older versions never downloaded. When offline, one
+/// that isn't cached yet is recorded in `skipped` rather than failing the
+/// whole load
+fn load_optional<T>(
+	cache_dir: &Path,
+	index: &HashMap<String, String>,
+	skipped: &mut Vec<String>,
+	manifest: &str,
+	load: fn(&Path, &str) -> Result<Vec<T>>) -> Result<Vec<T>>
+{
+	if live::is_offline() && !is_cached(cache_dir, index, manifest)
+	{
+		eprintln!("Skipping {manifest}, which can't be downloaded while offline");
+		skipped.push(manifest.to_owned());
+		return Ok(vec![])
+	}
+	let file_name = index.get(manifest)
+		.with_context(||format!("{manifest} is missing from the index"))?;
+	load(cache_dir, file_name)
+}
+
+/// Localised manifests in the index without a loader of their own, in a
+/// stable order. Leaves out the image manifest, which has no names
+fn other_manifests(index: &HashMap<String, String>) -> Vec<&str>
+{
+	let mut manifests: Vec<_> = index.keys()
+		.map(|m|m.as_str())
+		.filter(|m|m.starts_with("Export") && m.ends_with("_en.json"))
+		.filter(|m|!DEDICATED_MANIFESTS.contains(m))
+		.filter(|m|!FOUNDRY_ITEM_MANIFESTS.iter().any(|(f, _)|f == m))
+		.collect();
+	manifests.sort();
+	manifests
 }
 
 struct Interner<K, V>(HashMap<K, V>);
@@ -67,21 +179,59 @@ impl Data
 {
 	pub fn from_cache(cache_dir: &Path) -> Result<Self>
 	{
+		let index = cache::load_index(&cache_dir.join("index_en.txt.lzma"))?;
+		let key = snapshot::key(cache_dir, &index);
+		let mut data = match snapshot::load(cache_dir, &key)
+		{
+			Some(data)=>data,
+			None=>
+			{
+				let (data, skipped) = Self::from_manifests(cache_dir, &index)?;
+				if !skipped.is_empty()
+				{
+					// Rebuild next time rather than keep data missing manifests
+					eprintln!("Not saving a data snapshot, skipped {}", skipped.join(", "));
+				}
+				else if let Err(e) = snapshot::save(cache_dir, &key, &data)
+				{
+					eprintln!("Could not save data snapshot: {e:?}");
+				}
+				data
+			}
+		};
+		data.load_worldstate(cache_dir)?;
+		Ok(data)
+	}
+
+	/// (Data, Manifests that were skipped)
+	fn from_manifests(cache_dir: &Path, index: &HashMap<String, String>) -> Result<(Self, Vec<String>)>
+	{
+		let mut skipped = vec![];
 		let mut common_names: Interner<String, CommonName> = Interner::new();
 		let mut unique_names: Interner<String, UniqueName> = Interner::new();
-		let index = cache::load_index(&cache_dir.join("index_en.txt.lzma"))?;
 
 		let mut resources = Resources::default();
+		let mut ducats = Ducats::default();
+		let mut categories = Categories::default();
 		for resource in cache::load_resources(cache_dir, &index["ExportResources_en.json"])?
 		{
 			let common_name = common_names.intern(resource.name);
 			let unique_name = unique_names.intern(resource.unique_name);
+			categories.add(unique_name.clone(), Category::of_resource(unique_name.as_str()));
+			if let Some(price) = resource.prime_selling_price
+			{
+				ducats.add(unique_name.clone(), price);
+			}
 			resources.add(unique_name, common_name);
 		}
 
 		for warframe in cache::load_warframes(cache_dir, &index["ExportWarframes_en.json"])?
 		{
 			let unique_name = unique_names.intern(warframe.unique_name);
+			if let Some(category) = Category::from_product_category(&warframe.product_category)
+			{
+				categories.add(unique_name.clone(), category);
+			}
 			let common_name = warframe.name
 				.strip_prefix("<ARCHWING> ")
 				.unwrap_or(&warframe.name)
@@ -94,6 +244,14 @@ impl Data
 		{
 			let common_name = common_names.intern(weapon.name);
 			let unique_name = unique_names.intern(weapon.unique_name);
+			if let Some(category) = Category::from_product_category(&weapon.product_category)
+			{
+				categories.add(unique_name.clone(), category);
+			}
+			if let Some(price) = weapon.prime_selling_price
+			{
+				ducats.add(unique_name.clone(), price);
+			}
 			resources.add(unique_name, common_name);
 		}
 
@@ -101,6 +259,37 @@ impl Data
 		{
 			let common_name = common_names.intern(sentinel.name);
 			let unique_name = unique_names.intern(sentinel.unique_name);
+			let category = Category::from_product_category(&sentinel.product_category)
+				.unwrap_or(Category::Sentinel);
+			categories.add(unique_name.clone(), category);
+			resources.add(unique_name, common_name);
+		}
+
+		for (manifest, category) in FOUNDRY_ITEM_MANIFESTS
+		{
+			for item in load_optional(cache_dir, index, &mut skipped, manifest, cache::load_other)?
+			{
+				let common_name = common_names.intern(item.name);
+				let unique_name = unique_names.intern(item.unique_name);
+				categories.add(unique_name.clone(), category);
+				resources.add(unique_name, common_name);
+			}
+		}
+
+		// Loaded after the other item manifests, so items sharing a name with
+		// a mod are still found by name first
+		let mut upgrades = Upgrades::default();
+		for upgrade in load_optional(cache_dir, index, &mut skipped, "ExportUpgrades_en.json", cache::load_upgrades)?
+		{
+			let common_name = common_names.intern(upgrade.name);
+			let unique_name = unique_names.intern(upgrade.unique_name);
+			categories.add(unique_name.clone(), Category::Mod);
+			upgrades.add(unique_name.clone(), Upgrade
+			{
+				rarity: upgrade.rarity.and_then(|r|r.as_str().try_into().ok()),
+				polarity: upgrade.polarity.and_then(|p|p.as_str().try_into().ok()),
+				max_rank: upgrade.fusion_limit
+			});
 			resources.add(unique_name, common_name);
 		}
 
@@ -110,7 +299,20 @@ impl Data
 		{
 			let recipe_unique_name = unique_names.intern(recipe.unique_name);
 			let recipe_result_type = unique_names.intern(recipe.result_type);
-			recipes.add(recipe_unique_name.clone(), recipe_result_type);
+			categories.add(recipe_unique_name.clone(), Category::Blueprint);
+			if let Some(price) = recipe.prime_selling_price
+			{
+				ducats.add(recipe_unique_name.clone(), price);
+			}
+			let build_info = BuildInfo
+			{
+				credits: recipe.build_price,
+				build_time: Duration::from_secs(recipe.build_time.into()),
+				rush_cost: recipe.skip_build_time_price,
+				quantity: recipe.num.max(1),
+				consume_on_build: recipe.consume_on_build
+			};
+			recipes.add(recipe_unique_name.clone(), recipe_result_type, build_info);
 			for ingredient in recipe.ingredients
 			{
 				let ingredient_item_type = unique_names.intern(ingredient.item_type);
@@ -123,7 +325,8 @@ impl Data
 
 		let mut relics = Relics::default();
 		let mut relic_rewards = RelicRewards::default();
-		for relic in cache::load_relics(cache_dir, &index["ExportRelicArcane_en.json"])?
+		let (relic_manifest, arcanes) = cache::load_relics(cache_dir, &index["ExportRelicArcane_en.json"])?;
+		for relic in relic_manifest
 		{
 			let relic_unique_name = unique_names.intern(relic.unique_name);
 			let relic_common_name = common_names.intern(relic.name);
@@ -146,35 +349,310 @@ impl Data
 			}
 		}
 
+		for arcane in arcanes
+		{
+			let common_name = common_names.intern(arcane.name);
+			let unique_name = unique_names.intern(arcane.unique_name);
+			categories.add(unique_name.clone(), Category::Arcane);
+			upgrades.add(unique_name.clone(), Upgrade
+			{
+				rarity: arcane.rarity.and_then(|r|r.as_str().try_into().ok()),
+				polarity: None,
+				max_rank: arcane.max_rank
+			});
+			resources.add(unique_name, common_name);
+		}
+
+		// Everything else the game exports, so that it can at least be looked
+		// up by name. Items already loaded keep their entry
+		for manifest in other_manifests(index)
+		{
+			let items = match cache::load_other(cache_dir, &index[manifest])
+			{
+				Ok(items)=>items,
+				Err(e)=>
+				{
+					eprintln!("Skipping {manifest}: {e:#}");
+					skipped.push(manifest.to_owned());
+					continue;
+				}
+			};
+			for item in items
+			{
+				let unique_name = unique_names.intern(item.unique_name);
+				if resources.fetch_by_unique_name(unique_name.clone()).is_some()
+				{
+					continue;
+				}
+				if let Some(category) = Category::from_product_category(&item.product_category)
+				{
+					categories.add(unique_name.clone(), category);
+				}
+				resources.add(unique_name, common_names.intern(item.name));
+			}
+		}
+
+		let mut regions = Regions::default();
+		for region in load_optional(cache_dir, index, &mut skipped, "ExportRegions_en.json", cache::load_regions)?
+		{
+			regions.add(
+				unique_names.intern(region.unique_name),
+				common_names.intern(region.name),
+				common_names.intern(region.system_name));
+		}
+
+		let droptable = cache::load_droptable(&cache_dir.join("droptable.html"))?;
 		let mut active_relics = ActiveRelics::default();
-		for active_relic in cache::active_relics(&cache_dir.join("droptable.html"))?
+		let mut mission_rewards = MissionRewards::default();
+		for reward in droptable.mission_rewards
+		{
+			if active_relics::is_relic(&reward.reward.item)
+			{
+				let relic = reward.reward.item
+					.trim_end_matches(" (Radiant)")
+					.to_owned();
+				active_relics.add(common_names.intern(relic));
+			}
+			mission_rewards.add(MissionReward
+			{
+				planet: common_names.intern(reward.planet),
+				node: common_names.intern(reward.node),
+				mission_type: common_names.intern(reward.mission_type),
+				rotation: reward.rotation,
+				item: common_names.intern(reward.reward.item),
+				rarity: reward.reward.rarity,
+				chance: reward.reward.chance
+			});
+		}
+
+		let mut relic_chances = RelicChances::default();
+		for reward in droptable.relic_rewards
 		{
-			active_relics.add(active_relic.into());
+			relic_chances.add(RelicChance
+			{
+				relic: common_names.intern(reward.relic),
+				refinement: reward.refinement,
+				item: common_names.intern(reward.reward.item),
+				rarity: reward.reward.rarity,
+				chance: reward.reward.chance
+			});
 		}
 
-		let mut resurgence_relics = ResurgenceRelics::default();
-		for resurgence_relic in cache::resurgence_relics(&cache_dir.join("worldstate.json"))?
+		let mut location_rewards = LocationRewards::default();
+		for reward in droptable.location_rewards
 		{
-			resurgence_relics.add(resurgence_relic.into());
+			location_rewards.add(LocationReward
+			{
+				source: reward.source,
+				location: common_names.intern(reward.location),
+				rotation: reward.rotation,
+				stage: reward.stage.map(|s|common_names.intern(s)),
+				item: common_names.intern(reward.reward.item),
+				rarity: reward.reward.rarity,
+				chance: reward.reward.chance
+			});
 		}
 
-		let mut invasions = Invasions::default();
-		for invasion in cache::invasions(&cache_dir.join("worldstate.json"))?
+		let mut enemy_drops = EnemyDrops::default();
+		for drop in droptable.enemy_drops
 		{
-			invasions.add(invasion.into());
+			enemy_drops.add(EnemyDrop
+			{
+				kind: drop.kind,
+				enemy: common_names.intern(drop.enemy),
+				enemy_chance: drop.enemy_chance,
+				item: common_names.intern(drop.reward.item),
+				rarity: drop.reward.rarity,
+				chance: drop.reward.chance
+			});
 		}
 
-		Ok(Self
+		let data = Self
 		{
 			recipes,
 			relics,
 			requires,
 			resources,
-			invasions,
+			invasions: Invasions::default(),
 			active_relics,
-			resurgence_relics,
+			resurgence: Resurgence::default(),
 			relic_rewards,
-		})
+			mission_rewards,
+			relic_chances,
+			location_rewards,
+			enemy_drops,
+			regions,
+			ducats,
+			categories,
+			upgrades,
+			fissures: Fissures::default(),
+			void_traders: VoidTraders::default(),
+		};
+		Ok((data, skipped))
+	}
+
+	pub fn load_worldstate(&mut self, cache_dir: &Path) -> Result<()>
+	{
+		let mut resurgence = Resurgence::default();
+		let worldstate_resurgence = cache::resurgence(&cache_dir.join("worldstate.json"))?;
+		for offer in worldstate_resurgence.offers
+		{
+			resurgence.add(offer.item.into(), ResurgenceOffer
+			{
+				aya: offer.aya,
+				regal_aya: offer.regal_aya,
+				activation: offer.activation,
+				expiry: offer.expiry
+			});
+		}
+		for rotation in worldstate_resurgence.schedule
+		{
+			resurgence.add_rotation(rotation.item.into(), rotation.starts, rotation.expiry);
+		}
+
+		let mut invasions = Invasions::default();
+		for invasion in cache::invasions(&cache_dir.join("worldstate.json"))?
+		{
+			let into_rewards = |rewards: Vec<(String, u32)>|rewards
+				.into_iter()
+				.map(|(item, count)|(item.into(), count))
+				.collect();
+			invasions.add(Invasion
+			{
+				node: self.node_name(invasion.node),
+				attacker: invasion.attacker,
+				defender: invasion.defender,
+				attacker_rewards: into_rewards(invasion.attacker_rewards),
+				defender_rewards: into_rewards(invasion.defender_rewards),
+				attacker_progress: invasion.attacker_progress
+			});
+		}
+
+		let mut fissures = Fissures::default();
+		for fissure in cache::fissures(&cache_dir.join("worldstate.json"))?
+		{
+			fissures.add(Fissure
+			{
+				node: self.node_name(fissure.node),
+				mission_type: fissure.mission_type.into(),
+				tier: fissure.tier,
+				expiry: fissure.expiry,
+				steel_path: fissure.steel_path
+			});
+		}
+
+		self.resurgence = resurgence;
+		self.invasions = invasions;
+		let mut void_traders = VoidTraders::default();
+		for visit in cache::void_traders(&cache_dir.join("worldstate.json"))?
+		{
+			let items = visit.items
+				.into_iter()
+				.map(|i|VoidTraderItem
+				{
+					unique_name: i.item.into(),
+					ducats: i.ducats,
+					credits: i.credits
+				})
+				.collect();
+			// The worldstate uses an internal spelling of his name
+			let character = match visit.character.as_str()
+			{
+				"Baro'Ki Teel"=>"Baro Ki'Teer".to_owned(),
+				_=>visit.character
+			};
+			void_traders.add(VoidTrader
+			{
+				character: character.into(),
+				relay: self.relay_name(visit.node),
+				activation: visit.activation,
+				expiry: visit.expiry,
+				items
+			});
+		}
+
+		self.fissures = fissures;
+		self.void_traders = void_traders;
+		Ok(())
+	}
+
+	pub fn category(&self, unique_name: UniqueName) -> Option<Category>
+	{
+		self.categories.fetch_by_unique_name(unique_name)
+	}
+
+	/// Rarity, polarity and max rank of a mod or arcane
+	pub fn upgrade(&self, unique_name: UniqueName) -> Option<Upgrade>
+	{
+		self.upgrades.fetch_by_unique_name(unique_name)
+	}
+
+	/// Whether the item is a Prime part: a reward from any relic, vaulted or
+	/// not, that sells for ducats. Relics also hold common rewards such as
+	/// `Forma Blueprint`, which have no ducat value
+	pub fn is_prime_part(&self, unique_name: UniqueName) -> bool
+	{
+		self.ducats(unique_name.clone()).is_some()
+			&& self.relic_rewards
+				.fetch_by_reward_unique_name(unique_name)
+				.next()
+				.is_some()
+	}
+
+	/// What the item sells for at Baro Ki'Teer's kiosks
+	pub fn ducats(&self, unique_name: UniqueName) -> Option<u32>
+	{
+		self.ducats.fetch_by_unique_name(unique_name)
+	}
+
+	/// Star chart node name with its system, e.g. `Apollodorus (Mercury)`
+	fn node_name(&self, node: String) -> CommonName
+	{
+		match self.regions.fetch_by_unique_name(node.clone().into())
+		{
+			Some((name, system))=>format!("{name} ({system})").into(),
+			None=>node.into()
+		}
+	}
+
+	/// `PlutoHUB` to `Pluto Relay`
+	fn relay_name(&self, node: String) -> CommonName
+	{
+		match node.strip_suffix("HUB")
+		{
+			Some(planet)=>format!("{planet} Relay").into(),
+			None=>self.node_name(node)
+		}
+	}
+
+	/// Void Trader visits that haven't ended yet
+	pub fn void_traders(&self) -> impl Iterator<Item = &VoidTrader> + '_
+	{
+		self.void_traders.upcoming()
+	}
+
+	/// Featured items of the Prime Resurgence rotations that haven't started
+	/// yet, with when they start
+	pub fn upcoming_resurgence(&self) -> Vec<(CommonName, SystemTime)>
+	{
+		self.resurgence.upcoming()
+			.map(|(item, starts, _expiry)|
+			{
+				let common_name = self.item_common_name(item.clone())
+					.unwrap_or_else(||item.as_str()
+						.rsplit('/')
+						.next()
+						.unwrap_or_default()
+						.into());
+				(common_name, *starts)
+			})
+			.collect()
+	}
+
+	/// Void fissures that are still open, soonest to close first
+	pub fn fissures(&self) -> impl Iterator<Item = &Fissure> + '_
+	{
+		self.fissures.active()
 	}
 
 	pub fn requirements(&self, recipe_unique_name: UniqueName) -> impl Iterator<Item = (UniqueName, Count)> + '_
@@ -202,8 +680,18 @@ impl Data
 			.map(|(_item, count)|count)
 	}
 
+	/// Every relic with the item as a reward, vaulted or not
+	pub fn relics_containing(&self, unique_name: UniqueName) -> Vec<CommonName>
+	{
+		self.relic_rewards
+			.fetch_by_reward_unique_name(unique_name)
+			.flat_map(|(relic, _rarity)|self.relics.fetch_by_unique_name(relic))
+			.collect()
+	}
+
 	pub fn active_relics(&self, component_unique_name: UniqueName) -> Option<Vec<Relic>>
 	{
+		let item_common_name = self.item_common_name(component_unique_name.clone());
 		let relic_rewards = self.relic_rewards
 			.fetch_by_reward_unique_name(component_unique_name);
 
@@ -214,17 +702,20 @@ impl Data
 				.fetch_by_unique_name(relic_unique_name)?;
 			if self.active_relics.is_active(relic_common_name.clone())
 			{
-				let relic = Relic::new(relic_common_name, reward_rarity);
+				let drop_sources = self.drop_sources(relic_common_name.clone());
+				let chances = self.relic_chances(relic_common_name.clone(), item_common_name.clone());
+				let relic = Relic::new(relic_common_name, reward_rarity, drop_sources, chances, None);
 				relics.push(relic);
 			}
 		}
-		relics.sort();
-		relics.dedup();
+		relics.sort_by(|a, b|(a.name(), a.rarity).cmp(&(b.name(), b.rarity)));
+		relics.dedup_by(|a, b|(a.name(), a.rarity) == (b.name(), b.rarity));
 		Some(relics)
 	}
 
 	pub fn resurgence_relics(&self, component_unique_name: UniqueName) -> Option<Vec<Relic>>
 	{
+		let item_common_name = self.item_common_name(component_unique_name.clone());
 		let relic_rewards = self.relic_rewards
 			.fetch_by_reward_unique_name(component_unique_name);
 
@@ -233,14 +724,16 @@ impl Data
 		{
 			let relic_common_name = self.relics
 				.fetch_by_unique_name(relic_unique_name.clone())?;
-			if self.resurgence_relics.is_active(relic_unique_name.clone())
+			if let Some(offer) = self.resurgence.fetch_by_unique_name(relic_unique_name.clone())
 			{
-				let relic = Relic::new(relic_common_name, reward_rarity);
+				let drop_sources = self.drop_sources(relic_common_name.clone());
+				let chances = self.relic_chances(relic_common_name.clone(), item_common_name.clone());
+				let relic = Relic::new(relic_common_name, reward_rarity, drop_sources, chances, Some(offer));
 				relics.push(relic);
 			}
 		}
-		relics.sort();
-		relics.dedup();
+		relics.sort_by(|a, b|(a.name(), a.rarity).cmp(&(b.name(), b.rarity)));
+		relics.dedup_by(|a, b|(a.name(), a.rarity) == (b.name(), b.rarity));
 		Some(relics)
 	}
 
@@ -259,9 +752,229 @@ impl Data
 		self.recipes.fetch_by_unique_name(recipe_unique_name)
 	}
 
-	pub fn available_from_invasion(&self, unique_name: UniqueName) -> bool
+	pub fn recipe_build_info(&self, recipe_unique_name: UniqueName) -> Option<BuildInfo>
+	{
+		self.recipes.fetch_build_info_by_unique_name(recipe_unique_name)
+	}
+
+	/// Common name as used by the droptable. Blueprints are named after the
+	/// item they build
+	pub fn item_common_name(&self, unique_name: UniqueName) -> Option<CommonName>
+	{
+		self.resources.fetch_by_unique_name(unique_name.clone())
+			.or_else(||
+			{
+				let result = self.recipes.fetch_by_unique_name(unique_name)?;
+				let common_name = self.resources.fetch_by_unique_name(result)?;
+				Some(format!("{common_name} Blueprint").into())
+			})
+	}
+
+	/// Chance of an item at each refinement of a relic
+	fn relic_chances(&self, relic: CommonName, item: Option<CommonName>) -> Vec<(Refinement, f32)>
+	{
+		let Some(item) = item else {return vec![]};
+		self.relic_chances
+			.fetch_by_relic_item(relic, item)
+			.map(|c|(c.refinement, c.chance))
+			.collect()
+	}
+
+	/// Canonical common name of a relic, matched case insensitively
+	pub fn relic_common_name(&self, common_name: impl Into<CommonName>) -> Option<CommonName>
+	{
+		self.relics.fetch_by_common_name(common_name.into())
+			.map(|(_, common_name)|common_name)
+	}
+
+	/// Every reward of a relic, with its chance at each refinement
+	pub fn relic_contents(&self, relic_common_name: CommonName) -> Vec<RelicContent>
+	{
+		let Some((relic_unique_name, relic_common_name)) = self.relics
+			.fetch_by_common_name(relic_common_name)
+		else
+		{
+			return vec![]
+		};
+
+		self.relic_rewards
+			.fetch_by_relic_unique_name(relic_unique_name)
+			.map(|(reward, rarity)|
+			{
+				let item_common_name = self.item_common_name(reward.clone());
+				let chances = self.relic_chances(relic_common_name.clone(), item_common_name);
+				(reward, rarity, chances)
+			})
+			.collect()
+	}
+
+	/// Every mission, bounty and enemy that drops the item, best chance first
+	pub fn drop_sources(&self, common_name: CommonName) -> Vec<DropSource>
+	{
+		let missions = self.mission_rewards
+			.fetch_by_item(common_name.clone())
+			.map(|r|
+			{
+				let location = if r.planet.as_str().is_empty()
+				{
+					format!("{} ({})", r.node, r.mission_type)
+				}
+				else
+				{
+					format!("{}/{} ({})", r.planet, r.node, r.mission_type)
+				};
+				DropSource{location, rotation: r.rotation, chance: r.chance}
+			});
+		let locations = self.location_rewards
+			.fetch_by_item(common_name.clone())
+			.map(|r|
+			{
+				let location = match &r.stage
+				{
+					Some(stage) => format!("{}, {stage}", r.location),
+					None => r.location.to_string()
+				};
+				DropSource{location, rotation: r.rotation, chance: r.chance}
+			});
+		let enemies = self.enemy_drops
+			.fetch_by_item(common_name)
+			.map(|d|DropSource
+			{
+				location: d.enemy.to_string(),
+				rotation: None,
+				chance: d.enemy_chance * d.chance / 100.0
+			});
+
+		let mut sources: Vec<_> = missions.chain(locations).chain(enemies).collect();
+		sources.sort_by(|a, b|b.chance.total_cmp(&a.chance));
+		sources
+	}
+
+	/// Running invasions rewarding the item, and which side gives it
+	pub fn invasions(&self, unique_name: UniqueName) -> Vec<InvasionReward>
 	{
-		self.invasions.drops_from_invasion(unique_name)
+		let mut rewards = vec![];
+		for invasion in self.invasions.fetch_by_reward(unique_name.clone())
+		{
+			let sides = [
+				(&invasion.attacker_rewards, invasion.attacker, invasion.defender, invasion.attacker_progress),
+				(&invasion.defender_rewards, invasion.defender, invasion.attacker, 100.0 - invasion.attacker_progress)];
+			for (side_rewards, ally, enemy, progress) in sides
+			{
+				if let Some(&(_, count)) = side_rewards.iter().find(|(r, _)|*r == unique_name)
+				{
+					rewards.push(InvasionReward{node: invasion.node.clone(), ally, enemy, count, progress});
+				}
+			}
+		}
+		rewards
 	}
 
 }
+
+#[cfg(test)]
+impl Data
+{
+	/// Add a recipe for `result` built from `ingredients`
+	pub fn add_recipe(&mut self, recipe: &str, result: &str, build_info: BuildInfo, ingredients: &[(&str, u32)])
+	{
+		self.recipes.add(recipe.into(), result.into(), build_info);
+		for &(ingredient, count) in ingredients
+		{
+			self.requires.add(recipe.into(), ingredient.into(), count.into());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use std::time::{Duration, Instant};
+
+	use super::*;
+	use crate::invasion::Faction;
+
+	const LOOKUPS: usize = 10_000;
+
+	/// Data shaped like the manifests, with `items` craftable items that each
+	/// take a part and a resource, and drop from a relic
+	fn data(items: usize) -> Data
+	{
+		let mut data = Data::default();
+		for i in 0..items
+		{
+			let item: UniqueName = format!("/Lotus/Weapons/Item{i}").into();
+			let part: UniqueName = format!("/Lotus/Types/Recipes/Item{i}Barrel").into();
+			let recipe: UniqueName = format!("/Lotus/Types/Recipes/Item{i}Blueprint").into();
+			let relic: UniqueName = format!("/Lotus/Types/Game/Projections/Relic{i}").into();
+			data.resources.add(item.clone(), format!("Item {i}").into());
+			data.resources.add(part.clone(), format!("Item {i} Barrel").into());
+			data.recipes.add(recipe.clone(), item, BuildInfo::default());
+			data.requires.add(recipe.clone(), part.clone(), 1.into());
+			data.requires.add(recipe, "/Lotus/Types/Items/MiscItems/Ferrite".into(), 100.into());
+			data.relics.add(relic.clone(), format!("Lith I{i} Relic").into());
+			data.relic_rewards.add(relic, part.clone(), Rarity::Rare);
+			data.ducats.add(part, 45);
+		}
+		data
+	}
+
+	/// Time for `LOOKUPS` rounds of the lookups a tracked item does, spread
+	/// over every item
+	fn lookup_time(data: &Data, items: usize) -> Duration
+	{
+		let start = Instant::now();
+		for i in (0..LOOKUPS).map(|i|i * items / LOOKUPS)
+		{
+			let item = data.resource_unique_name(format!("item {i}")).unwrap();
+			assert!(data.resource_common_name(item.clone()).is_some());
+			let recipe = data.recipe(item).unwrap();
+			let (part, _) = data.requirements(recipe).next().unwrap();
+			assert!(data.is_prime_part(part.clone()));
+			assert_eq!(data.relics_containing(part).len(), 1);
+		}
+		start.elapsed()
+	}
+
+	#[test]
+	fn invasion_progress_is_flipped_for_the_defender()
+	{
+		let dir = std::env::temp_dir().join("structures_test_invasions");
+		std::fs::create_dir_all(&dir).unwrap();
+		std::fs::write(dir.join("worldstate.json"), r#"{"Invasions": [{
+			"Node": "SolNode1",
+			"Faction": "FC_GRINEER",
+			"DefenderFaction": "FC_CORPUS",
+			"Count": -2500,
+			"Goal": 10000,
+			"AttackerReward": {"countedItems": [{"ItemType": "/Lotus/Types/Attacker", "ItemCount": 1}]},
+			"DefenderReward": {"countedItems": [{"ItemType": "/Lotus/Types/Defender", "ItemCount": 3}]}
+		}], "PrimeVaultTraders": []}"#).unwrap();
+		let mut data = Data::default();
+		data.load_worldstate(&dir).unwrap();
+
+		let attacker = &data.invasions("/Lotus/Types/Attacker".into())[0];
+		assert_eq!((attacker.ally, attacker.enemy), (Faction::Grineer, Faction::Corpus));
+		assert_eq!((attacker.count, attacker.progress), (1, 37.5));
+		let defender = &data.invasions("/Lotus/Types/Defender".into())[0];
+		assert_eq!((defender.ally, defender.enemy), (Faction::Corpus, Faction::Grineer));
+		assert_eq!((defender.count, defender.progress), (3, 62.5));
+		assert_eq!(defender.node, "SolNode1".into());
+	}
+
+	/// Lookup cost as the manifests grow. Timing depends on the machine, so
+	/// run it by hand with `cargo test --release -- --ignored --nocapture`
+	#[test]
+	#[ignore]
+	fn bench_lookups_as_manifests_grow()
+	{
+		for items in [1_000, 10_000, 100_000]
+		{
+			let data = data(items);
+			let elapsed = lookup_time(&data, items);
+			println!(
+				"{items:>7} items: {:?} per lookup round",
+				elapsed / LOOKUPS as u32);
+		}
+	}
+}
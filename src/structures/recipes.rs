@@ -1,12 +1,48 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::index::{fold, Index};
 use super::types::UniqueName;
 
-/// (Unique Name, Result Type)
-type Row = (UniqueName, UniqueName);
+/// Foundry requirements and output of a single craft
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo
+{
+	pub credits: u32,
+	pub build_time: Duration,
+	/// Platinum needed to skip the build time
+	pub rush_cost: u32,
+	/// Units produced per craft
+	pub quantity: u32,
+	/// Whether the blueprint is used up by the craft
+	pub consume_on_build: bool
+}
 
-#[derive(Default, Debug)]
+impl Default for BuildInfo
+{
+	fn default() -> Self
+	{
+		Self
+		{
+			credits: 0,
+			build_time: Duration::ZERO,
+			rush_cost: 0,
+			quantity: 1,
+			consume_on_build: true
+		}
+	}
+}
+
+/// (Unique Name, Result Type, Build Info)
+type Row = (UniqueName, UniqueName, BuildInfo);
+
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct Recipes
 {
-	pub rows: Vec<Row>
+	rows: Vec<Row>,
+	by_unique_name: Index<String>,
+	by_result_type: Index<UniqueName>
 }
 
 impl Recipes
@@ -15,31 +51,85 @@ impl Recipes
 		&self,
 		unique_name: UniqueName) -> Option<UniqueName>
 	{
-		let unique_name = unique_name.as_str();
-		self.rows
-			.iter()
-			.filter(|row|row.0.as_str().eq_ignore_ascii_case(unique_name))
-			.map(|row|&row.1)
+		self.by_unique_name
+			.get(&fold(unique_name.as_str()))
+			.map(|i|&self.rows[i].1)
 			.next()
 			.cloned()
 	}
 
+	pub fn fetch_build_info_by_unique_name(
+		&self,
+		unique_name: UniqueName) -> Option<BuildInfo>
+	{
+		self.by_unique_name
+			.get(&fold(unique_name.as_str()))
+			.map(|i|self.rows[i].2)
+			.next()
+	}
+
 	pub fn fetch_by_result_type(
 		&self,
 		result_type: UniqueName) -> impl Iterator<Item = UniqueName> + '_
 	{
-		self.rows
-			.iter()
-			.filter(move |&row|row.1.as_str() == result_type.as_str())
-			.map(|row|&row.0)
-			.cloned()
+		self.by_result_type
+			.get(&result_type)
+			.map(|i|self.rows[i].0.clone())
 	}
 
 	pub fn add(
 		&mut self,
 		unique_name: UniqueName,
-		result_type: UniqueName)
+		result_type: UniqueName,
+		build_info: BuildInfo)
+	{
+		let row = self.rows.len();
+		self.by_unique_name.add(fold(unique_name.as_str()), row);
+		self.by_result_type.add(result_type.clone(), row);
+		self.rows.push((unique_name.clone(), result_type.clone(), build_info));
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn build_info(credits: u32) -> BuildInfo
 	{
-		self.rows.push((unique_name.clone(), result_type.clone()));
+		BuildInfo{credits, ..BuildInfo::default()}
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn fetch_by_unique_name_is_case_insensitive()
+	{
+		let mut recipes = Recipes::default();
+		recipes.add("/Lotus/Recipes/BratonBlueprint".into(), "/Lotus/Weapons/Braton".into(), build_info(15_000));
+		assert_eq!(
+			recipes.fetch_by_unique_name("/lotus/recipes/bratonblueprint".into()),
+			Some("/Lotus/Weapons/Braton".into()));
+		assert_eq!(
+			recipes.fetch_build_info_by_unique_name("/LOTUS/RECIPES/BRATONBLUEPRINT".into()),
+			Some(build_info(15_000)));
+	}
+
+	#[test]
+	fn fetch_keeps_insertion_order()
+	{
+		let mut recipes = Recipes::default();
+		for i in 0..1_000
+		{
+			recipes.add(format!("/Lotus/Recipes/Filler{i}").into(), format!("/Lotus/Items/Filler{i}").into(), build_info(i));
+		}
+		recipes.add("/Lotus/Recipes/Second".into(), "/Lotus/Items/Result".into(), build_info(1));
+		recipes.add("/Lotus/Recipes/First".into(), "/Lotus/Items/Result".into(), build_info(2));
+		recipes.add("/Lotus/Recipes/Second".into(), "/Lotus/Items/Other".into(), build_info(3));
+
+		let found: Vec<UniqueName> = recipes.fetch_by_result_type("/Lotus/Items/Result".into()).collect();
+		assert_eq!(found, vec![UniqueName::from("/Lotus/Recipes/Second"), UniqueName::from("/Lotus/Recipes/First")]);
+		assert_eq!(
+			recipes.fetch_build_info_by_unique_name("/Lotus/Recipes/Second".into()),
+			Some(build_info(1)));
+		assert_eq!(recipes.fetch_by_result_type("/Lotus/Items/Missing".into()).count(), 0);
+	}
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use super::index::Index;
+use super::types::{UniqueName, CommonName};
+
+/// (Node, Name, System)
+type Row = (UniqueName, CommonName, CommonName);
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct Regions
+{
+	rows: Vec<Row>,
+	by_unique_name: Index<UniqueName>
+}
+
+impl Regions
+{
+	/// Node name and the system it is in
+	pub fn fetch_by_unique_name(
+		&self,
+		unique_name: UniqueName) -> Option<(CommonName, CommonName)>
+	{
+		self.by_unique_name
+			.get(&unique_name)
+			.map(|i|&self.rows[i])
+			.map(|row|(row.1.clone(), row.2.clone()))
+			.next()
+	}
+
+	pub fn add(
+		&mut self,
+		unique_name: UniqueName,
+		name: CommonName,
+		system_name: CommonName)
+	{
+		self.by_unique_name.add(unique_name.clone(), self.rows.len());
+		self.rows.push((unique_name, name, system_name));
+	}
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use crate::drop::DropRarity;
+use crate::relic::Refinement;
+use super::index::{fold, item_key, Index};
+use super::types::CommonName;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RelicChance
+{
+	pub relic: CommonName,
+	pub refinement: Refinement,
+	pub item: CommonName,
+	pub rarity: DropRarity,
+	/// Percent
+	pub chance: f32
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct RelicChances
+{
+	rows: Vec<RelicChance>,
+	/// (Relic, Item)
+	by_relic_item: Index<(String, String)>
+}
+
+impl RelicChances
+{
+	pub fn fetch_by_relic_item(
+		&self,
+		relic: CommonName,
+		item: CommonName) -> impl Iterator<Item = &RelicChance> + '_
+	{
+		let key = (fold(relic.as_str()), item_key(item.as_str()));
+		self.by_relic_item
+			.get(&key)
+			.map(|i|&self.rows[i])
+	}
+
+	pub fn add(&mut self, chance: RelicChance)
+	{
+		let key = (fold(chance.relic.as_str()), item_key(chance.item.as_str()));
+		self.by_relic_item.add(key, self.rows.len());
+		self.rows.push(chance);
+	}
+}
@@ -1,13 +1,18 @@
+use serde::{Deserialize, Serialize};
+
 use crate::relic::Rarity;
+use super::index::Index;
 use super::types::UniqueName;
 
 /// (Relic, Reward, Rarity)
 type Row = (UniqueName, UniqueName, Rarity);
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct RelicRewards
 {
-	rows: Vec<Row>
+	rows: Vec<Row>,
+	by_relic_unique_name: Index<UniqueName>,
+	by_reward_unique_name: Index<UniqueName>
 }
 
 impl RelicRewards
@@ -16,21 +21,34 @@ impl RelicRewards
 		&self,
 		unique_name: UniqueName) -> impl Iterator<Item = (UniqueName, Rarity)> + '_
 	{
-		self.rows
-			.iter()
-			.filter(move |&row|row.1.as_str() == unique_name.as_str())
+		self.by_reward_unique_name
+			.get(&unique_name)
+			.map(|i|&self.rows[i])
 			.map(|row|(row.0.clone(), row.2))
 	}
 
+	pub fn fetch_by_relic_unique_name(
+		&self,
+		unique_name: UniqueName) -> impl Iterator<Item = (UniqueName, Rarity)> + '_
+	{
+		self.by_relic_unique_name
+			.get(&unique_name)
+			.map(|i|&self.rows[i])
+			.map(|row|(row.1.clone(), row.2))
+	}
+
 	pub fn add(
 		&mut self,
 		relic_unique_name: UniqueName,
 		reward_unique_name: UniqueName,
 		reward_rarity: Rarity)
 	{
+		let row = self.rows.len();
+		self.by_relic_unique_name.add(relic_unique_name.clone(), row);
+		self.by_reward_unique_name.add(reward_unique_name.clone(), row);
 		self.rows.push((
 			relic_unique_name.clone(),
 			reward_unique_name.clone(),
 			reward_rarity));
 	}
-}
\ No newline at end of file
+}
@@ -1,11 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+use super::index::{fold, Index};
 use super::types::{UniqueName, CommonName};
 
 type Row = (UniqueName, CommonName);
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct Relics
 {
-	rows: Vec<Row>
+	rows: Vec<Row>,
+	by_unique_name: Index<String>,
+	by_common_name: Index<String>
 }
 
 impl Relics
@@ -14,10 +19,20 @@ impl Relics
 		&self,
 		unique_name: UniqueName) -> Option<CommonName>
 	{
-		self.rows
-			.iter()
-			.filter(|row|row.0.as_str().eq_ignore_ascii_case(unique_name.as_str()))
-			.map(|row|&row.1)
+		self.by_unique_name
+			.get(&fold(unique_name.as_str()))
+			.map(|i|&self.rows[i].1)
+			.next()
+			.cloned()
+	}
+
+	pub fn fetch_by_common_name(
+		&self,
+		common_name: CommonName) -> Option<(UniqueName, CommonName)>
+	{
+		self.by_common_name
+			.get(&fold(common_name.as_str()))
+			.map(|i|&self.rows[i])
 			.next()
 			.cloned()
 	}
@@ -27,6 +42,9 @@ impl Relics
 		unique_name: UniqueName,
 		common_name: CommonName)
 	{
+		let row = self.rows.len();
+		self.by_unique_name.add(fold(unique_name.as_str()), row);
+		self.by_common_name.add(fold(common_name.as_str()), row);
 		self.rows.push((unique_name.clone(), common_name.clone()));
 	}
-}
\ No newline at end of file
+}
@@ -1,12 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+use super::index::Index;
 use super::types::{Count, UniqueName};
 
 /// Recipe, Item Type, Count
 type Row = (UniqueName, UniqueName, Count);
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct Requires
 {
-	rows: Vec<Row>
+	rows: Vec<Row>,
+	by_recipe_unique_name: Index<UniqueName>
 }
 
 impl Requires
@@ -15,9 +19,9 @@ impl Requires
 		&self,
 		recipe_unique_name: UniqueName) -> impl Iterator<Item = (UniqueName, Count)> + '_
 	{
-		self.rows
-			.iter()
-			.filter(move |&row|row.0.as_str() == recipe_unique_name.as_str())
+		self.by_recipe_unique_name
+			.get(&recipe_unique_name)
+			.map(|i|&self.rows[i])
 			.map(|row|(row.1.clone(), row.2))
 	}
 
@@ -27,6 +31,7 @@ impl Requires
 		item_type: UniqueName,
 		count: Count)
 	{
+		self.by_recipe_unique_name.add(recipe_unique_name.clone(), self.rows.len());
 		self.rows.push((recipe_unique_name.clone(), item_type.clone(), count));
 	}
-}
\ No newline at end of file
+}
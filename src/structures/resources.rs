@@ -1,12 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+use super::index::{fold, Index};
 use super::types::{UniqueName, CommonName};
 
 
 type Row = (UniqueName, CommonName);
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct Resources
 {
-	rows: Vec<Row>
+	rows: Vec<Row>,
+	by_unique_name: Index<String>,
+	by_common_name: Index<String>
 }
 
 impl Resources
@@ -15,11 +20,9 @@ impl Resources
 		&self,
 		unique_name: UniqueName) -> Option<CommonName>
 	{
-		let unique_name = unique_name.as_str();
-		self.rows
-			.iter()
-			.filter(|row|row.0.as_str().eq_ignore_ascii_case(unique_name))
-			.map(|row|&row.1)
+		self.by_unique_name
+			.get(&fold(unique_name.as_str()))
+			.map(|i|&self.rows[i].1)
 			.next()
 			.cloned()
 	}
@@ -28,17 +31,63 @@ impl Resources
 		&self,
 		common_name: CommonName) -> Option<UniqueName>
 	{
-		let common_name = common_name.as_str();
-		self.rows
-			.iter()
-			.filter(|row|row.1.as_str().eq_ignore_ascii_case(common_name))
-			.map(|row|&row.0)
-			.nth(0)
+		self.by_common_name
+			.get(&fold(common_name.as_str()))
+			.map(|i|&self.rows[i].0)
+			.next()
 			.cloned()
 	}
 
 	pub fn add(&mut self, unique_name: UniqueName, common_name: CommonName)
 	{
+		let row = self.rows.len();
+		self.by_unique_name.add(fold(unique_name.as_str()), row);
+		self.by_common_name.add(fold(common_name.as_str()), row);
 		self.rows.push((unique_name.clone(), common_name.clone()));
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn table(rows: usize) -> Resources
+	{
+		let mut resources = Resources::default();
+		for i in 0..rows
+		{
+			resources.add(
+				format!("/Lotus/Types/Items/Item{i}").into(),
+				format!("Item {i}").into());
+		}
+		resources
+	}
+
+	#[test]
+	fn fetch_is_case_insensitive()
+	{
+		let resources = table(10);
+		assert_eq!(
+			resources.fetch_by_common_name("ITEM 3".into()),
+			Some("/Lotus/Types/Items/Item3".into()));
+		assert_eq!(
+			resources.fetch_by_unique_name("/lotus/types/items/item3".into()),
+			Some("Item 3".into()));
+		assert_eq!(resources.fetch_by_common_name("Item 10".into()), None);
+	}
+
+	#[test]
+	fn fetch_returns_first_match()
+	{
+		let mut resources = table(10);
+		resources.add("/Lotus/Types/Items/Duplicate".into(), "Item 3".into());
+		resources.add("/Lotus/Types/Items/Item3".into(), "Duplicate".into());
+		assert_eq!(
+			resources.fetch_by_common_name("Item 3".into()),
+			Some("/Lotus/Types/Items/Item3".into()));
+		assert_eq!(
+			resources.fetch_by_unique_name("/Lotus/Types/Items/Item3".into()),
+			Some("Item 3".into()));
+	}
+}
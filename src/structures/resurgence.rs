@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use super::types::UniqueName;
+
+/// Price and availability of an item sold during Prime Resurgence
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResurgenceOffer
+{
+	pub aya: Option<u32>,
+	pub regal_aya: Option<u32>,
+	pub activation: SystemTime,
+	pub expiry: SystemTime
+}
+
+/// (Featured item, Starts, Expiry)
+type Rotation = (UniqueName, SystemTime, SystemTime);
+
+#[derive(Default, Debug)]
+pub struct Resurgence
+{
+	offers: HashMap<UniqueName, ResurgenceOffer>,
+	/// Oldest rotation first
+	schedule: Vec<Rotation>
+}
+
+impl Resurgence
+{
+	pub fn fetch_by_unique_name(&self, unique_name: UniqueName) -> Option<ResurgenceOffer>
+	{
+		self.offers.get(&unique_name).copied()
+	}
+
+	/// Rotations that haven't started yet
+	pub fn upcoming(&self) -> impl Iterator<Item = &Rotation> + '_
+	{
+		let now = SystemTime::now();
+		self.schedule
+			.iter()
+			.filter(move |(_, starts, _)|*starts > now)
+	}
+
+	pub fn add(&mut self, unique_name: UniqueName, offer: ResurgenceOffer)
+	{
+		self.offers.insert(unique_name, offer);
+	}
+
+	pub fn add_rotation(&mut self, featured_item: UniqueName, starts: SystemTime, expiry: SystemTime)
+	{
+		self.schedule.push((featured_item, starts, expiry));
+	}
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::Data;
+
+/// Bump whenever the layout of `Data` changes
+const VERSION: u32 = 14;
+const FILE_NAME: &str = "data.bin";
+
+/// Identifies the inputs a snapshot was built from. The manifest entries carry
+/// their content hash in the file name, the droptable is hashed directly
+pub fn key(cache_dir: &Path, index: &HashMap<String, String>) -> Vec<String>
+{
+	let mut key: Vec<_> = index.values().cloned().collect();
+	key.sort();
+
+	let droptable = match std::fs::read(cache_dir.join("droptable.html"))
+	{
+		Ok(contents)=>format!("droptable.html!{:016x}", fnv1a(&contents)),
+		Err(_)=>"droptable.html!missing".to_owned()
+	};
+	key.push(droptable);
+	key
+}
+
+/// 64 bit FNV-1a, which unlike `DefaultHasher` gives the same hash across
+/// Rust releases
+fn fnv1a(bytes: &[u8]) -> u64
+{
+	bytes.iter().fold(0xcbf29ce484222325, |hash, &b|(hash ^ u64::from(b)).wrapping_mul(0x100000001b3))
+}
+
+/// Load a snapshot, as long as it was built by this version from the same inputs
+pub fn load(cache_dir: &Path, key: &[String]) -> Option<Data>
+{
+	let file = File::open(cache_dir.join(FILE_NAME)).ok()?;
+	let mut reader = BufReader::new(file);
+	let version: u32 = bincode::deserialize_from(&mut reader).ok()?;
+	if version != VERSION
+	{
+		return None
+	}
+	let saved_key: Vec<String> = bincode::deserialize_from(&mut reader).ok()?;
+	if saved_key != key
+	{
+		return None
+	}
+	bincode::deserialize_from(&mut reader).ok()
+}
+
+pub fn save(cache_dir: &Path, key: &[String], data: &Data) -> Result<()>
+{
+	let file = File::create(cache_dir.join(FILE_NAME))
+		.context("Creating snapshot file")?;
+	let mut writer = BufWriter::new(file);
+	bincode::serialize_into(&mut writer, &VERSION)
+		.and_then(|_|bincode::serialize_into(&mut writer, key))
+		.and_then(|_|bincode::serialize_into(&mut writer, data))
+		.context("Writing snapshot file")
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	#[test]
+	fn fnv1a_matches_the_reference_values()
+	{
+		assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+		assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+		assert_eq!(fnv1a(b"foobar"), 0x85944171f73967e8);
+	}
+}
@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::sync::Arc;
 use std::fmt::Display;
 
 use serde::{Serialize, Deserialize};
@@ -6,7 +6,7 @@ use serde::{Serialize, Deserialize};
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(into = "String")]
 #[serde(from = "String")]
-pub struct UniqueName(Rc<str>);
+pub struct UniqueName(Arc<str>);
 
 impl UniqueName
 {
@@ -72,8 +72,10 @@ impl PartialEq<UniqueName> for str
 	}
 }
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub struct CommonName(Rc<str>);
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "String")]
+#[serde(from = "String")]
+pub struct CommonName(Arc<str>);
 
 impl CommonName
 {
@@ -107,6 +109,14 @@ impl From<&str> for CommonName
 	}
 }
 
+impl From<CommonName> for String
+{
+	fn from(i: CommonName) -> Self
+	{
+		i.0.to_string()
+	}
+}
+
 impl From<UniqueName> for CommonName
 {
 	fn from(i: UniqueName) -> Self
@@ -115,7 +125,7 @@ impl From<UniqueName> for CommonName
 	}
 }
 
-#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Count(pub u32);
 
 impl Count
@@ -140,4 +150,21 @@ impl Display for Count
 	{
 		self.0.fmt(f)
 	}
-}
\ No newline at end of file
+}
+
+impl std::ops::Mul for Count
+{
+	type Output = Self;
+	fn mul(self, rhs: Self) -> Self::Output
+	{
+		Self(self.0 * rhs.0)
+	}
+}
+
+impl std::ops::AddAssign for Count
+{
+	fn add_assign(&mut self, rhs: Self)
+	{
+		self.0 += rhs.0;
+	}
+}
@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::upgrade::{Polarity, UpgradeRarity};
+use super::types::UniqueName;
+
+/// A mod or arcane
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Upgrade
+{
+	pub rarity: Option<UpgradeRarity>,
+	/// Arcanes have none
+	pub polarity: Option<Polarity>,
+	pub max_rank: u32
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct Upgrades
+{
+	by_unique_name: HashMap<UniqueName, Upgrade>
+}
+
+impl Upgrades
+{
+	pub fn fetch_by_unique_name(&self, unique_name: UniqueName) -> Option<Upgrade>
+	{
+		self.by_unique_name.get(&unique_name).copied()
+	}
+
+	pub fn add(&mut self, unique_name: UniqueName, upgrade: Upgrade)
+	{
+		self.by_unique_name.insert(unique_name, upgrade);
+	}
+}
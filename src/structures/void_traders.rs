@@ -0,0 +1,56 @@
+use std::time::SystemTime;
+
+use super::types::{CommonName, UniqueName};
+
+#[derive(Clone, Debug)]
+pub struct VoidTraderItem
+{
+	pub unique_name: UniqueName,
+	pub ducats: Option<u32>,
+	pub credits: Option<u32>
+}
+
+/// A visit from Baro Ki'Teer
+#[derive(Clone, Debug)]
+pub struct VoidTrader
+{
+	pub character: CommonName,
+	/// e.g. `Pluto Relay`
+	pub relay: CommonName,
+	pub activation: SystemTime,
+	pub expiry: SystemTime,
+	/// Empty until the trader arrives
+	pub items: Vec<VoidTraderItem>
+}
+
+impl VoidTrader
+{
+	pub fn is_here(&self) -> bool
+	{
+		let now = SystemTime::now();
+		self.activation <= now && now < self.expiry
+	}
+}
+
+#[derive(Default, Debug)]
+pub struct VoidTraders
+{
+	rows: Vec<VoidTrader>
+}
+
+impl VoidTraders
+{
+	/// Visits that haven't ended yet
+	pub fn upcoming(&self) -> impl Iterator<Item = &VoidTrader> + '_
+	{
+		let now = SystemTime::now();
+		self.rows
+			.iter()
+			.filter(move |t|t.expiry > now)
+	}
+
+	pub fn add(&mut self, trader: VoidTrader)
+	{
+		self.rows.push(trader);
+	}
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use crate::structures::{CommonName, Count, Data, UniqueName};
+use crate::Tracked;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Entry
+{
+	pub common_name: CommonName,
+	pub unique_name: UniqueName,
+	/// Owned, but not needed by any tracked item
+	pub surplus: Count,
+	/// Ducats for a single one
+	pub ducats: u32
+}
+
+impl Entry
+{
+	pub fn total_ducats(&self) -> u32
+	{
+		self.surplus.to_u32() * self.ducats
+	}
+}
+
+/// Owned prime parts left over once every tracked item has taken what it
+/// still needs, and so are safe to sell for ducats
+pub fn surplus(
+	db: &Data,
+	tracked: &[Tracked],
+	owned: &HashMap<UniqueName, u32>) -> Vec<Entry>
+{
+	// Building the trees takes what they need out of the pool
+	let mut available = owned.clone();
+	let mut missing = HashMap::new();
+	for build_tree in tracked.iter().flat_map(|t|&t.build_trees)
+	{
+		build_tree.missing(&mut available, &mut missing);
+	}
+
+	let mut entries: Vec<_> = available.into_iter()
+		.filter(|&(_, surplus)|surplus > 0)
+		.flat_map(|(unique_name, surplus)|
+		{
+			let ducats = db.ducats(unique_name.clone())?;
+			let common_name = db.item_common_name(unique_name.clone())
+				.unwrap_or_else(||unique_name.clone().into());
+			Some(Entry{common_name, unique_name, surplus: surplus.into(), ducats})
+		})
+		.collect();
+	entries.sort();
+	entries
+}
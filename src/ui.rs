@@ -1,24 +1,69 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, SystemTime};
 
+use crate::availability::{self, Availability};
 use crate::cache;
 use crate::Data;
+use crate::drop::DropSource;
+use crate::foundry::{self, Build};
+use crate::invasion::InvasionReward;
 use crate::item_view::ItemView;
-use crate::relic::Rarity;
-use crate::structures::{Count, UniqueName};
+use crate::planner;
+use crate::refresh::{self, Message};
+use crate::relic::{Rarity, Refinement, Relic, RelicInventory, RelicTier};
+use crate::shopping_list::{self, shopping_list};
+use crate::surplus::surplus;
+use crate::structures::{CommonName, Count, ResurgenceOffer, UniqueName, Upgrade, VoidTrader, VoidTraderItem};
 use crate::Tracked;
 
 use eframe::egui;
 use egui::Ui;
 use egui::Color32;
 
+const RADSHARE_SQUAD: u32 = 4;
+/// Resurgence relics leaving sooner than this are highlighted
+const RESURGENCE_WARNING: Duration = Duration::from_secs(3 * 24 * 60 * 60);
+/// How many of the best drop sources to list under each component
+const SHOWN_DROP_SOURCES: usize = 3;
+
+/// Which optional panels are open
+#[derive(Default)]
+struct Panels
+{
+	shopping_list: bool,
+	surplus: bool,
+	relic_planner: bool,
+	fissures: bool
+}
+
+/// What a tracked item shows from the worldstate, worked out once per frame
+struct Status<'a>
+{
+	availability: Option<Availability>,
+	/// Void trader items used by the tracked item
+	void_trader_offers: Vec<(&'a VoidTrader, &'a VoidTraderItem)>
+}
+
 pub struct App
 {
 	db: Data,
 	tracked: Vec<Tracked>,
 	owned: HashMap<UniqueName, u32>,
+	foundry: Vec<Build>,
+	relics: RelicInventory,
 	add_search: String,
 	to_remove: Option<usize>,
+	panels: Panels,
+	/// Only show tracked items with this availability
+	availability_filter: Option<Availability>,
+	relic_search: String,
+	relic_refinement: Refinement,
+	/// Modification time of each cached download, when running offline
+	offline: Option<Vec<(&'static str, SystemTime)>>,
+	refresh: Option<Receiver<Message>>,
+	refresh_status: String,
 	cache_dir: PathBuf
 }
 
@@ -26,48 +71,217 @@ impl App
 {
 	pub fn with_state(
 		db: Data,
-		tracked: Vec<Tracked>,
-		owned: HashMap<UniqueName, u32>,
+		state: cache::State,
+		offline: Option<Vec<(&'static str, SystemTime)>>,
+		refresh: Option<Receiver<Message>>,
 		cache_dir: PathBuf) -> Self
 	{
+		let cache::State{tracked, owned, foundry, relics} = state;
 		Self
 		{
 			db,
 			tracked,
 			owned,
+			foundry,
+			relics,
 			add_search: String::new(),
 			to_remove: None,
+			panels: Panels::default(),
+			availability_filter: None,
+			relic_search: String::new(),
+			relic_refinement: Refinement::Intact,
+			offline,
+			refresh,
+			refresh_status: String::new(),
 			cache_dir
 		}
 	}
+
+	fn handle_refresh(&mut self)
+	{
+		let Some(refresh) = &self.refresh
+		else
+		{
+			return
+		};
+
+		let mut reload = false;
+		for message in refresh.try_iter()
+		{
+			match message
+			{
+				Message::Status(status) => self.refresh_status = status,
+				Message::Data(db) =>
+				{
+					self.db = *db;
+					self.offline = None;
+					reload = true;
+				},
+				Message::Worldstate => match self.db.load_worldstate(&self.cache_dir)
+				{
+					Ok(()) =>
+					{
+						self.offline = None;
+						reload = true;
+					},
+					Err(e) => self.refresh_status = format!("Reloading world state: {e:#}")
+				},
+				Message::Failed(error) =>
+				{
+					self.refresh_status = error;
+					self.offline = Some(refresh::cached_file_times(&self.cache_dir));
+				}
+			}
+		}
+
+		// Tracked items hold on to drop information from the old data
+		if reload
+		{
+			let tracked = std::mem::take(&mut self.tracked);
+			for tracked in tracked
+			{
+				match Tracked::new(&self.db, tracked.unique_name.clone())
+				{
+					Ok(t) => self.tracked.push(t),
+					Err(e) =>
+					{
+						eprintln!("Could not reload {}: {e:?}", tracked.unique_name);
+						self.tracked.push(tracked);
+					}
+				}
+			}
+		}
+	}
 }
 
 impl eframe::App for App
 {
 	fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>)
 	{
-		let tracked = std::mem::take(&mut self.tracked);
-		let owned = std::mem::take(&mut self.owned);
-		cache::save_state(
-			&self.cache_dir.join("tracked.json"),
-			tracked,
-			owned).unwrap();
+		let state = cache::State
+		{
+			tracked: std::mem::take(&mut self.tracked),
+			owned: std::mem::take(&mut self.owned),
+			foundry: std::mem::take(&mut self.foundry),
+			relics: std::mem::take(&mut self.relics)
+		};
+		cache::save_state(&self.cache_dir.join("tracked.json"), state).unwrap();
 	}
 
 	fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame)
 	{
 		if let Some(i)=self.to_remove.take(){self.tracked.remove(i);}
+		self.handle_refresh();
+		foundry::collect_finished(&self.db, &mut self.foundry, &mut self.owned);
+		if !self.foundry.is_empty()
+		{
+			// Keep the timers ticking
+			ctx.request_repaint_after(Duration::from_secs(1));
+		}
 		ctx.set_visuals(egui::style::Visuals::dark());
+		// Shared by every panel that needs it, since it walks every build tree
+		let shopping = if self.panels.shopping_list || self.panels.fissures || self.panels.relic_planner
+		{
+			shopping_list(&self.db, &self.tracked, &self.owned)
+		}
+		else
+		{
+			vec![]
+		};
+		if !self.foundry.is_empty()
+		{
+			egui::TopBottomPanel::bottom("foundry").show(ctx, |ui|
+			{
+				foundry_panel(ui, &self.db, &mut self.foundry);
+			});
+		}
+		if self.panels.shopping_list
+		{
+			egui::SidePanel::right("shopping_list").show(ctx, |ui|
+			{
+				shopping_list_panel(ui, &shopping);
+			});
+		}
+		if self.panels.surplus
+		{
+			egui::SidePanel::right("surplus").show(ctx, |ui|
+			{
+				surplus_panel(ui, &self.db, &self.tracked, &self.owned);
+			});
+		}
+		if self.panels.fissures
+		{
+			egui::SidePanel::right("fissures").show(ctx, |ui|
+			{
+				fissures_panel(ui, &self.db, &shopping, &self.relics);
+			});
+		}
+		if self.panels.relic_planner
+		{
+			egui::SidePanel::left("relic_planner").show(ctx, |ui|
+			{
+				relic_planner_panel(
+					ui,
+					&self.db,
+					&shopping,
+					&mut self.relics,
+					&mut self.relic_search,
+					&mut self.relic_refinement);
+			});
+		}
 		egui::CentralPanel::default().show(ctx, |ui|
 		{
-			header(ui, &mut self.add_search, &self.db, &mut self.tracked);
+			header(
+				ui,
+				&mut self.add_search,
+				&mut self.panels,
+				&mut self.availability_filter,
+				&self.db,
+				&mut self.tracked);
+			if let Some(cached_file_times) = &self.offline
+			{
+				offline_banner(ui, cached_file_times);
+			}
+			if !self.refresh_status.is_empty()
+			{
+				ui.label(self.refresh_status.as_str());
+			}
+			let statuses: Vec<_> = self.tracked.iter()
+				.map(|t|Status
+				{
+					availability: availability::of_tracked(&self.db, &self.relics, t),
+					void_trader_offers: void_trader_offers(&self.db, t)
+				})
+				.collect();
+			let wanted: HashSet<_> = statuses.iter()
+				.flat_map(|s|&s.void_trader_offers)
+				.map(|(_, offer)|&offer.unique_name)
+				.collect();
+			void_trader_banner(ui, &self.db, &wanted);
+			resurgence_banner(ui, &self.db.upcoming_resurgence());
 			egui::Grid::new("").show(ui, |ui|
 			{
-				for (i, tracked) in self.tracked.iter().enumerate()
+				let shown = self.tracked.iter()
+					.zip(&statuses)
+					.enumerate()
+					.filter(|(_, (_, s))|self.availability_filter.is_none()
+						|| s.availability == self.availability_filter);
+				for (column, (i, (tracked, status))) in shown.enumerate()
 				{
-					item(ui, tracked, i, &mut self.owned, &mut self.to_remove);
+					let removed = item(
+						ui,
+						&self.db,
+						tracked,
+						status,
+						&mut self.owned,
+						&mut self.foundry,
+						&self.relics);
+					if removed
+					{
+						self.to_remove = Some(i);
+					}
 
-					if i%7 == 6
+					if column%7 == 6
 					{
 						ui.end_row()
 					}
@@ -80,6 +294,8 @@ impl eframe::App for App
 fn header(
 	ui: &mut Ui,
 	add_search: &mut String,
+	panels: &mut Panels,
+	availability_filter: &mut Option<Availability>,
 	db: &Data,
 	tracked: &mut Vec<Tracked>)
 {
@@ -91,27 +307,244 @@ fn header(
 		if ui.button("Add").clicked()
 		{
 			if let Some(unique_name) = db.resource_unique_name(add_search.as_str())
+				&& let Ok(t) = Tracked::new(db, unique_name)
 			{
-				if let Ok(t) = Tracked::new(db, unique_name)
-				{
-					tracked.push(t);
-				}
-				
+				tracked.push(t);
 			}
 			add_search.clear();
 			tracked.sort_by(|a, b| a.common_name.cmp(&b.common_name));
 		}
+		ui.toggle_value(&mut panels.shopping_list, "Shopping List");
+		ui.toggle_value(&mut panels.surplus, "Surplus");
+		ui.toggle_value(&mut panels.relic_planner, "Relic Planner");
+		ui.toggle_value(&mut panels.fissures, "Fissures");
+		let selected = availability_filter.map(|a|a.to_string())
+			.unwrap_or_else(||"All".to_owned());
+		egui::ComboBox::from_label("Show")
+			.selected_text(selected)
+			.show_ui(ui, |ui|
+			{
+				ui.selectable_value(availability_filter, None, "All");
+				for availability in Availability::ALL
+				{
+					ui.selectable_value(availability_filter, Some(availability), availability.to_string());
+				}
+			});
+	});
+}
+
+fn offline_banner(ui: &mut Ui, cached_file_times: &[(&str, SystemTime)])
+{
+	ui.horizontal(|ui|
+	{
+		ui.colored_label(Color32::YELLOW, "Offline");
+		for (file_name, modified) in cached_file_times
+		{
+			let age = modified.elapsed()
+				.unwrap_or_default();
+			ui.label(format!("{file_name}: {} old", format_duration(age)));
+		}
+	});
+}
+
+fn shopping_list_panel(ui: &mut Ui, shopping: &[shopping_list::Entry])
+{
+	ui.heading("Shopping List");
+	egui::ScrollArea::vertical().show(ui, |ui|
+	{
+		egui::Grid::new("shopping_list_grid").striped(true).show(ui, |ui|
+		{
+			for entry in shopping
+			{
+				ui.label(entry.common_name.as_str());
+				ui.label(format!("{} missing", entry.missing));
+				ui.label(format!("of {}", entry.required));
+				ui.end_row();
+			}
+		});
+	});
+}
+
+/// Owned prime parts no tracked item needs, and what they sell for
+fn surplus_panel(
+	ui: &mut Ui,
+	db: &Data,
+	tracked: &[Tracked],
+	owned_components: &HashMap<UniqueName, u32>)
+{
+	ui.heading("Surplus");
+	let entries = surplus(db, tracked, owned_components);
+	let total: u32 = entries.iter().map(|e|e.total_ducats()).sum();
+	ui.label(format!("{total} Ducats in total"));
+	egui::ScrollArea::vertical().show(ui, |ui|
+	{
+		egui::Grid::new("surplus_grid").striped(true).show(ui, |ui|
+		{
+			for entry in entries
+			{
+				ui.label(entry.common_name.as_str());
+				ui.label(format!("{} spare", entry.surplus));
+				ui.label(format!("{} Ducats each", entry.ducats));
+				ui.label(format!("{} Ducats", entry.total_ducats()));
+				ui.end_row();
+			}
+		});
+	});
+}
+
+fn relic_planner_panel(
+	ui: &mut Ui,
+	db: &Data,
+	shopping: &[shopping_list::Entry],
+	relics: &mut RelicInventory,
+	relic_search: &mut String,
+	relic_refinement: &mut Refinement)
+{
+	ui.heading("Relic Planner");
+	ui.horizontal(|ui|
+	{
+		ui.text_edit_singleline(relic_search);
+		egui::ComboBox::from_id_salt("relic_refinement")
+			.selected_text(relic_refinement.to_string())
+			.show_ui(ui, |ui|
+			{
+				for refinement in Refinement::ALL
+				{
+					ui.selectable_value(relic_refinement, refinement, refinement.to_string());
+				}
+			});
+		if ui.button("Add").clicked()
+		{
+			if let Some(relic) = db.relic_common_name(relic_search.as_str())
+			{
+				*relics.entry(relic)
+					.or_default()
+					.entry(*relic_refinement)
+					.or_default() += 1;
+			}
+			relic_search.clear();
+		}
 	});
+
+	let needed: HashSet<_> = shopping.iter()
+		.map(|e|e.unique_name.clone())
+		.collect();
+	let suggestions = planner::suggestions(db, relics, &needed);
+
+	egui::ScrollArea::vertical().show(ui, |ui|
+	{
+		ui.label("Crack next");
+		egui::Grid::new("relic_suggestions_grid").striped(true).show(ui, |ui|
+		{
+			for suggestion in &suggestions
+			{
+				ui.label(format!("{} ({})", suggestion.relic, suggestion.refinement));
+				ui.label(format!("x{}", suggestion.owned));
+				ui.label(format!("{:.1}%", suggestion.chance))
+					.on_hover_text(suggestion.needed.iter()
+						.map(|n|n.as_str())
+						.collect::<Vec<_>>()
+						.join("\n"));
+				ui.end_row();
+			}
+		});
+
+		ui.separator();
+		ui.label("Inventory");
+		let mut relic_names: Vec<_> = relics.keys().cloned().collect();
+		relic_names.sort();
+		egui::Grid::new("relic_inventory_grid").striped(true).show(ui, |ui|
+		{
+			for relic in relic_names
+			{
+				ui.label(relic.as_str());
+				let counts = relics.entry(relic).or_default();
+				for refinement in Refinement::ALL
+				{
+					let count = counts.entry(refinement).or_default();
+					ui.add(egui::DragValue::new(count).prefix(format!("{refinement}: ")));
+				}
+				ui.end_row();
+			}
+		});
+	});
+	relics.retain(|_, counts|counts.values().any(|&c|c > 0));
 }
 
+/// Fissures where a relic with a still needed item can be opened are highlighted
+fn fissures_panel(
+	ui: &mut Ui,
+	db: &Data,
+	shopping: &[shopping_list::Entry],
+	relics: &RelicInventory)
+{
+	ui.heading("Void Fissures");
+	let needed: HashSet<_> = shopping.iter()
+		.map(|e|e.unique_name.clone())
+		.collect();
+	let useful_tiers = planner::useful_tiers(db, relics, &needed);
+	let is_useful = |tier: RelicTier|useful_tiers.iter().any(|t|t.opens_in(tier));
+
+	egui::ScrollArea::vertical().show(ui, |ui|
+	{
+		egui::Grid::new("fissures_grid").striped(true).show(ui, |ui|
+		{
+			for fissure in db.fissures()
+			{
+				let color = if is_useful(fissure.tier) {Color32::GREEN} else {ui.visuals().text_color()};
+				ui.colored_label(color, fissure.tier.to_string());
+				ui.colored_label(color, fissure.node.as_str());
+				ui.colored_label(color, fissure.mission_type.as_str());
+				ui.label(if fissure.steel_path {"Steel Path"} else {""});
+				let remaining = fissure.expiry.duration_since(SystemTime::now())
+					.unwrap_or_default();
+				ui.label(format_duration(remaining));
+				ui.end_row();
+			}
+		});
+	});
+}
+
+fn foundry_panel(ui: &mut Ui, db: &Data, foundry: &mut Vec<Build>)
+{
+	ui.heading("Foundry");
+	let mut to_cancel = None;
+	ui.horizontal_wrapped(|ui|
+	{
+		for (i, build) in foundry.iter().enumerate()
+		{
+			let common_name = db.recipe_result(build.recipe.clone())
+				.and_then(|r|db.resource_common_name(r))
+				.unwrap_or_else(||build.recipe.clone().into());
+			ui.group(|ui|
+			{
+				ui.label(common_name.as_str());
+				ui.label(format_duration(build.remaining()));
+				if ui.button("Cancel").clicked()
+				{
+					to_cancel = Some(i);
+				}
+			});
+		}
+	});
+	if let Some(i) = to_cancel
+	{
+		foundry.remove(i);
+	}
+}
+
+/// Returns whether the item should stop being tracked
 fn item(
 	ui: &mut Ui,
+	db: &Data,
 	tracked: &Tracked,
-	i: usize,
+	status: &Status,
 	owned_components: &mut HashMap<UniqueName, u32>,
-	to_remove: &mut Option<usize>)
+	foundry: &mut Vec<Build>,
+	relics: &RelicInventory) -> bool
 {
 	let common_name = tracked.common_name.clone();
+	let mut removed = false;
 	ui.group(|ui|
 	{
 		ui.vertical(|ui|
@@ -120,39 +553,150 @@ fn item(
 			{
 				if ui.button("Del").clicked()
 				{
-					*to_remove = Some(i)
+					removed = true;
 				};
 				ui.heading(common_name.as_str());
+				if let Some(category) = db.category(tracked.unique_name.clone())
+				{
+					ui.label(category.to_string());
+				}
+				if let Some(availability) = status.availability
+				{
+					availability_badge(ui, availability);
+				}
 			});
+			ui.label(format!(
+				"{} credits, {} foundry",
+				tracked.credits(),
+				format_duration(tracked.build_time())));
+			for (trader, offer) in &status.void_trader_offers
+			{
+				let common_name = db.item_common_name(offer.unique_name.clone())
+					.unwrap_or_else(||offer.unique_name.clone().into());
+				ui.colored_label(Color32::GOLD, format!(
+					"{} sells {common_name} for {}",
+					trader.character,
+					void_trader_price(offer)));
+			}
+			if let Some(goal) = &tracked.goal
+			{
+				if let Some(upgrade) = db.upgrade(goal.unique_name())
+				{
+					ui.label(upgrade_summary(upgrade));
+				}
+				component_group(ui, db, owned_components, foundry, relics, goal, 1.into());
+			}
 			ui.horizontal(|ui|
 			{
 				for (recipe, components) in &tracked.recipes
 				{
-					recipe_group(ui, recipe, components, owned_components);
+					recipe_group(ui, db, recipe, components, owned_components, foundry, relics);
 				}
 			});
+			raw_resources(ui, db, tracked, owned_components);
 		});
 	});
+	removed
+}
+
+/// e.g. `Rare Madurai, max rank 10`
+fn upgrade_summary(upgrade: Upgrade) -> String
+{
+	let kind: Vec<_> = upgrade.rarity
+		.map(|r|r.to_string())
+		.into_iter()
+		.chain(upgrade.polarity.map(|p|p.to_string()))
+		.collect();
+	if kind.is_empty()
+	{
+		format!("Max rank {}", upgrade.max_rank)
+	}
+	else
+	{
+		format!("{}, max rank {}", kind.join(" "), upgrade.max_rank)
+	}
+}
+
+fn availability_badge(ui: &mut Ui, availability: Availability)
+{
+	let color = match availability
+	{
+		Availability::Available=>Color32::GREEN,
+		Availability::Resurgence=>Color32::LIGHT_BLUE,
+		Availability::OwnedRelics=>Color32::YELLOW,
+		Availability::Vaulted=>Color32::RED
+	};
+	ui.colored_label(color, availability.to_string());
+}
+
+fn raw_resources(
+	ui: &mut Ui,
+	db: &Data,
+	tracked: &Tracked,
+	owned_components: &HashMap<UniqueName, u32>)
+{
+	let mut raw_resources: Vec<_> = tracked.raw_resources()
+		.into_iter()
+		.map(|(unique_name, count)|
+		{
+			let common_name = db.resource_common_name(unique_name.clone())
+				.unwrap_or_else(||unique_name.clone().into());
+			(common_name, unique_name, count)
+		})
+		.collect();
+	raw_resources.sort();
+
+	egui::CollapsingHeader::new("Raw Resources")
+		.id_salt(tracked.unique_name.as_str())
+		.show(ui, |ui|
+		{
+			for (common_name, unique_name, required) in raw_resources
+			{
+				let owned = owned_components.get(&unique_name)
+					.copied()
+					.unwrap_or_default();
+				ui.label(format!("{owned} of {required} {common_name}"));
+			}
+		});
 }
 
 fn recipe_group(
 	ui: &mut Ui,
+	db: &Data,
 	recipe: &crate::Recipe,
 	components: &[(crate::Requirement, Count)],
-	owned_components: &mut HashMap<UniqueName, u32>)
+	owned_components: &mut HashMap<UniqueName, u32>,
+	foundry: &mut Vec<Build>,
+	relics: &RelicInventory)
 {
 	ui.vertical(|ui|
 	{
 		component_group(
 			ui,
+			db,
 			owned_components,
+			foundry,
+			relics,
 			recipe,
 			1.into());
+		let quantity = match recipe.output_quantity()
+		{
+			1 => String::new(),
+			n => format!("x{n}, ")
+		};
+		ui.label(format!(
+			"{quantity}{} credits, {} ({} plat to rush)",
+			recipe.credits(),
+			format_duration(recipe.build_time()),
+			recipe.rush_cost()));
 		for (component, required) in components
 		{
 			component_group(
 				ui,
+				db,
 				owned_components,
+				foundry,
+				relics,
 				component,
 				required.to_owned());
 		}
@@ -160,15 +704,17 @@ fn recipe_group(
 }
 fn component_group(
 	ui: &mut Ui,
+	db: &Data,
 	owned_components: &mut HashMap<UniqueName, u32>,
+	foundry: &mut Vec<Build>,
+	relics: &RelicInventory,
 	item: impl ItemView,
 	required: Count)
 {
 	let owned = owned_components.entry(item.unique_name())
 		.or_default();
 	let fullfilled = *owned >= required.to_u32();
-	let color = fullfilled.then_some(Color32::BLACK)
-		.unwrap_or_else(||ui.visuals().text_color());
+	let color = if fullfilled {Color32::BLACK} else {ui.visuals().text_color()};
 
 	ui.vertical(|ui|
 	{
@@ -186,7 +732,29 @@ fn component_group(
 			
 			ui.colored_label(color, format!("{owned} of {required}"));
 			ui.colored_label(color, item.common_name().as_str());
-			
+			let ducats = db.ducats(item.unique_name())
+				.or_else(||db.ducats(item.recipe_unique_name()?));
+			if let Some(ducats) = ducats
+			{
+				ui.colored_label(color, format!("{ducats} Ducats"));
+			}
+			if let Some(availability) = availability::of_item(db, relics, &item)
+			{
+				availability_badge(ui, availability);
+			}
+
+			if let Some(recipe) = item.recipe_unique_name()
+			{
+				match foundry.iter().find(|b|b.recipe == recipe)
+				{
+					Some(build) => {ui.label(format_duration(build.remaining()));},
+					None if !fullfilled && ui.button("Build").clicked() =>
+					{
+						foundry.push(Build::new(db, recipe));
+					},
+					None => {}
+				}
+			}
 		});
 
 		// No need to bother showing drop information if we already have it
@@ -208,10 +776,27 @@ fn component_group(
 						Rarity::Uncommon=>Color32::GRAY,
 						Rarity::Rare=>Color32::GOLD
 					};
-		
-					ui.colored_label(color, relic.name());
+
+					// Recommend the best place to farm the relic, with the
+					// rest on hover
+					let label = match relic.drop_sources().first()
+					{
+						Some(best) => format!("{} - {}", relic.name(), drop_source_text(best)),
+						None => relic.name().to_owned()
+					};
+					let sources = relic.drop_sources()
+						.iter()
+						.map(drop_source_text)
+						.collect::<Vec<_>>()
+						.join("\n");
+					let response = ui.colored_label(color, label);
+					if !sources.is_empty()
+					{
+						response.on_hover_text(sources);
+					}
 				}
 			});
+			expected_runs(ui, active_relics, required.to_u32() - *owned);
 		}
 
 		let resurgence_relics = item.resurgence_relics();
@@ -229,14 +814,198 @@ fn component_group(
 						Rarity::Rare=>Color32::GOLD
 					};
 		
-					ui.colored_label(color, relic.name());
+					ui.horizontal(|ui|
+					{
+						ui.colored_label(color, relic.name());
+						if let Some(offer) = relic.resurgence()
+						{
+							resurgence_offer(ui, offer);
+						}
+					});
 				}
 			});
 		}
 
-		if item.available_from_invasion()
+		let invasions = item.invasions();
+		if !invasions.is_empty()
+		{
+			ui.menu_button("Invasion", |ui|
+			{
+				invasion_details(ui, invasions);
+			});
+		}
+
+		let drop_sources = item.drop_sources();
+		if !drop_sources.is_empty()
 		{
-			ui.label("Invasion");
+			ui.label("Drops From");
+			for source in drop_sources.iter().take(SHOWN_DROP_SOURCES)
+			{
+				ui.label(drop_source_text(source));
+			}
 		}
 	});
-}
\ No newline at end of file
+}
+
+/// Expected relic runs for the missing count, using the relic most likely to
+/// drop the item at each refinement, since rarities scale differently
+fn expected_runs(ui: &mut Ui, relics: &[Relic], missing: u32)
+{
+	let best = |refinement: Refinement, squad_size: u32|relics.iter()
+		.filter_map(|r|Some((r, r.expected_runs(refinement, squad_size, missing)?)))
+		.min_by(|(_, a), (_, b)|a.total_cmp(b));
+	if best(Refinement::Intact, 1).is_none() && best(Refinement::Radiant, 1).is_none()
+	{
+		return
+	}
+
+	let runs = |refinement, squad_size|match best(refinement, squad_size)
+	{
+		Some((relic, runs))=>format!("{runs:.0} ({})", relic.name()),
+		None=>"?".to_owned()
+	};
+	ui.label("Expected runs");
+	ui.label(format!(
+		"Solo: {} intact, {} radiant",
+		runs(Refinement::Intact, 1),
+		runs(Refinement::Radiant, 1)));
+	ui.label(format!("Radshare: {}", runs(Refinement::Radiant, RADSHARE_SQUAD)));
+}
+
+fn drop_source_text(source: &DropSource) -> String
+{
+	let rotation = source.rotation
+		.map(|r|format!(" Rot {r}"))
+		.unwrap_or_default();
+	format!("{}{rotation}: {:.2}%", source.location, source.chance)
+}
+
+/// Items on sale from void traders at a relay right now that are used by
+/// `tracked`
+fn void_trader_offers<'a>(db: &'a Data, tracked: &Tracked) -> Vec<(&'a VoidTrader, &'a VoidTraderItem)>
+{
+	db.void_traders()
+		.filter(|t|t.is_here())
+		.flat_map(|t|t.items.iter().map(move |i|(t, i)))
+		.filter(|(_, i)|tracked.contains(&i.unique_name))
+		.collect()
+}
+
+/// Where Baro Ki'Teer is, and whether he is selling anything in `wanted`
+fn void_trader_banner(ui: &mut Ui, db: &Data, wanted: &HashSet<&UniqueName>)
+{
+	let now = SystemTime::now();
+	for trader in db.void_traders()
+	{
+		ui.horizontal(|ui|
+		{
+			if trader.is_here()
+			{
+				let leaves = trader.expiry.duration_since(now)
+					.unwrap_or_default();
+				ui.label(format!(
+					"{} is at {}, leaves in {}",
+					trader.character,
+					trader.relay,
+					format_duration(leaves)));
+				let wanted = trader.items.iter()
+					.filter(|i|wanted.contains(&i.unique_name))
+					.count();
+				if wanted > 0
+				{
+					ui.colored_label(Color32::GOLD, format!("Selling {wanted} tracked items"));
+				}
+			}
+			else
+			{
+				let arrives = trader.activation.duration_since(now)
+					.unwrap_or_default();
+				ui.label(format!(
+					"{} arrives at {} in {}",
+					trader.character,
+					trader.relay,
+					format_duration(arrives)));
+			}
+		});
+	}
+}
+
+fn void_trader_price(item: &VoidTraderItem) -> String
+{
+	match (item.ducats, item.credits)
+	{
+		(Some(ducats), Some(credits))=>format!("{ducats} Ducats + {credits} Credits"),
+		(Some(ducats), None)=>format!("{ducats} Ducats"),
+		(None, Some(credits))=>format!("{credits} Credits"),
+		(None, None)=>String::new()
+	}
+}
+
+/// Which invasions to run and what they give
+fn invasion_details(ui: &mut Ui, invasions: &[InvasionReward])
+{
+	egui::Grid::new("invasion_details_grid").striped(true).show(ui, |ui|
+	{
+		for invasion in invasions
+		{
+			ui.label(invasion.node.as_str());
+			ui.label(format!("Support {} against {}", invasion.ally, invasion.enemy));
+			ui.label(format!("{}x", invasion.count));
+			ui.label(format!("{:.0}% won", invasion.progress));
+			ui.end_row();
+		}
+	});
+}
+
+/// Featured items of the Prime Resurgence rotations still to come
+fn resurgence_banner(ui: &mut Ui, upcoming: &[(CommonName, SystemTime)])
+{
+	if upcoming.is_empty()
+	{
+		return
+	}
+	let now = SystemTime::now();
+	ui.horizontal_wrapped(|ui|
+	{
+		ui.label("Coming to Resurgence:");
+		for (item, starts) in upcoming
+		{
+			let starts_in = starts.duration_since(now)
+				.unwrap_or_default();
+			ui.label(format!("{item} in {}", format_duration(starts_in)));
+		}
+	});
+}
+
+/// Price of a Resurgence item and how long until it leaves
+fn resurgence_offer(ui: &mut Ui, offer: ResurgenceOffer)
+{
+	let price = match (offer.aya, offer.regal_aya)
+	{
+		(Some(aya), _)=>format!("{aya} Aya"),
+		(None, Some(regal_aya))=>format!("{regal_aya} Regal Aya"),
+		(None, None)=>String::new()
+	};
+	let now = SystemTime::now();
+	let remaining = offer.expiry.duration_since(now)
+		.unwrap_or_default();
+	let on_sale = now.duration_since(offer.activation)
+		.unwrap_or_default();
+	let color = if remaining < RESURGENCE_WARNING {Color32::YELLOW} else {ui.visuals().text_color()};
+	ui.label(price);
+	ui.colored_label(color, format!("leaves Resurgence in {}", format_duration(remaining)))
+		.on_hover_text(format!("On sale for {}", format_duration(on_sale)));
+}
+
+fn format_duration(duration: Duration) -> String
+{
+	let minutes = duration.as_secs() / 60;
+	let hours = minutes / 60;
+	match (hours / 24, hours % 24, minutes % 60)
+	{
+		(0, 0, m) => format!("{m}m"),
+		(0, h, 0) => format!("{h}h"),
+		(0, h, m) => format!("{h}h {m}m"),
+		(d, h, _) => format!("{d}d {h}h")
+	}
+}
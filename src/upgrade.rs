@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+use anyhow::{anyhow, Result, Error};
+use serde::{Deserialize, Serialize};
+
+/// Rarity of a mod or arcane
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum UpgradeRarity
+{
+	Common,
+	Uncommon,
+	Rare,
+	Legendary
+}
+
+impl TryFrom<&str> for UpgradeRarity
+{
+	type Error = Error;
+	fn try_from(i: &str) -> Result<Self, Self::Error>
+	{
+		match i
+		{
+			"COMMON"=>Ok(Self::Common),
+			"UNCOMMON"=>Ok(Self::Uncommon),
+			"RARE"=>Ok(Self::Rare),
+			"LEGENDARY"=>Ok(Self::Legendary),
+			_=>Err(anyhow!("Unknown upgrade rarity: {}", i))
+		}
+	}
+}
+
+impl Display for UpgradeRarity
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			Self::Common=>"Common".fmt(f),
+			Self::Uncommon=>"Uncommon".fmt(f),
+			Self::Rare=>"Rare".fmt(f),
+			Self::Legendary=>"Legendary".fmt(f)
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Polarity
+{
+	Madurai,
+	Vazarin,
+	Naramon,
+	Zenurik,
+	Unairu,
+	Penjaga,
+	Umbra,
+	Universal,
+	Any
+}
+
+impl TryFrom<&str> for Polarity
+{
+	type Error = Error;
+	fn try_from(i: &str) -> Result<Self, Self::Error>
+	{
+		match i
+		{
+			"AP_ATTACK"=>Ok(Self::Madurai),
+			"AP_DEFENSE"=>Ok(Self::Vazarin),
+			"AP_TACTIC"=>Ok(Self::Naramon),
+			"AP_POWER"=>Ok(Self::Zenurik),
+			"AP_WARD"=>Ok(Self::Unairu),
+			"AP_PRECEPT"=>Ok(Self::Penjaga),
+			"AP_UMBRA"=>Ok(Self::Umbra),
+			"AP_UNIVERSAL"=>Ok(Self::Universal),
+			"AP_ANY"=>Ok(Self::Any),
+			_=>Err(anyhow!("Unknown polarity: {}", i))
+		}
+	}
+}
+
+impl Display for Polarity
+{
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+	{
+		match self
+		{
+			Self::Madurai=>"Madurai".fmt(f),
+			Self::Vazarin=>"Vazarin".fmt(f),
+			Self::Naramon=>"Naramon".fmt(f),
+			Self::Zenurik=>"Zenurik".fmt(f),
+			Self::Unairu=>"Unairu".fmt(f),
+			Self::Penjaga=>"Penjaga".fmt(f),
+			Self::Umbra=>"Umbra".fmt(f),
+			Self::Universal=>"Universal".fmt(f),
+			Self::Any=>"Any".fmt(f)
+		}
+	}
+}